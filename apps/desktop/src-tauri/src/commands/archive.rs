@@ -0,0 +1,43 @@
+use crate::models::{Document, Project};
+use crate::services::archive_service;
+use super::ensure_project_available;
+use std::path::PathBuf;
+
+/// Zips a project into an archive
+#[tauri::command]
+pub async fn archive_project(project_path: String, output_path: String) -> Result<String, String> {
+    let path = PathBuf::from(project_path);
+    ensure_project_available(&path)?;
+    let output = PathBuf::from(output_path);
+
+    archive_service::archive_project(&path, &output)
+        .map(|p| p.to_string_lossy().to_string())
+        .map_err(|e| format!("Failed to archive project: {}", e))
+}
+
+/// Opens an archived project for read-only browsing
+#[tauri::command]
+pub async fn open_archive_readonly(zip_path: String) -> Result<Project, String> {
+    let path = PathBuf::from(zip_path);
+
+    archive_service::open_archive_readonly(&path)
+        .map_err(|e| format!("Failed to open archive: {}", e))
+}
+
+/// Lists the documents inside an archive
+#[tauri::command]
+pub async fn list_archive_documents(zip_path: String) -> Result<Vec<Document>, String> {
+    let path = PathBuf::from(zip_path);
+
+    archive_service::list_archive_documents(&path)
+        .map_err(|e| format!("Failed to list archive documents: {}", e))
+}
+
+/// Reads a document from inside an archive
+#[tauri::command]
+pub async fn read_archive_document(document_path: String) -> Result<Document, String> {
+    let path = PathBuf::from(document_path);
+
+    archive_service::read_archive_document(&path)
+        .map_err(|e| format!("Failed to read archive document: {}", e))
+}
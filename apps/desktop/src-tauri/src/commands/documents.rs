@@ -1,5 +1,8 @@
-use crate::models::{Document, DocumentType};
-use crate::services::document_service;
+use crate::models::{
+    Document, DocumentMeta, DocumentSort, DocumentType, DuplicateSet, KeyInfo, PromoteOutcome, ProseWarning, ReplaceOptions,
+};
+use crate::services::{document_service, duplicate_service, meta_cache_service, prose_service};
+use super::{ensure_project_available, ensure_project_writable};
 use std::path::PathBuf;
 
 /// Creates a new document in the project
@@ -12,6 +15,7 @@ pub async fn create_document(
     subcategory: Option<String>,
 ) -> Result<Document, String> {
     let path = PathBuf::from(project_path);
+    ensure_project_writable(&path)?;
     let subcat = subcategory.as_deref();
 
     // Parse document type
@@ -43,6 +47,40 @@ pub async fn update_document(document_path: String, content: String) -> Result<(
         .map_err(|e| format!("Failed to update document: {}", e))
 }
 
+/// Replaces all matches, or only the nth one, within a document's body
+#[tauri::command]
+pub async fn replace_in_document(
+    document_path: String,
+    find: String,
+    replace: String,
+    options: Option<ReplaceOptions>,
+    occurrence: Option<usize>,
+) -> Result<Document, String> {
+    let path = PathBuf::from(document_path);
+    let options = options.unwrap_or_default();
+
+    document_service::replace_in_document(&path, &find, &replace, &options, occurrence)
+        .map_err(|e| format!("Failed to replace in document: {}", e))
+}
+
+/// Bumps a document's modification time without changing its content
+#[tauri::command]
+pub async fn touch_document(document_path: String) -> Result<Document, String> {
+    let path = PathBuf::from(document_path);
+
+    document_service::touch_document(&path)
+        .map_err(|e| format!("Failed to touch document: {}", e))
+}
+
+/// Saves a document immediately, refreshing its word count at most once per interval
+#[tauri::command]
+pub async fn quick_save_document(document_path: String, content: String) -> Result<Document, String> {
+    let path = PathBuf::from(document_path);
+
+    document_service::quick_save_document(&path, &content)
+        .map_err(|e| format!("Failed to save document: {}", e))
+}
+
 /// Deletes a document
 #[tauri::command]
 pub async fn delete_document(document_path: String) -> Result<(), String> {
@@ -52,6 +90,76 @@ pub async fn delete_document(document_path: String) -> Result<(), String> {
         .map_err(|e| format!("Failed to delete document: {}", e))
 }
 
+/// Moves a document into another category
+#[tauri::command]
+pub async fn move_document(
+    project_path: String,
+    document_path: String,
+    category: String,
+    subcategory: Option<String>,
+) -> Result<Document, String> {
+    let project = PathBuf::from(project_path);
+    ensure_project_writable(&project)?;
+    let path = PathBuf::from(document_path);
+
+    document_service::move_document(&project, &path, &category, subcategory.as_deref())
+        .map_err(|e| format!("Failed to move document: {}", e))
+}
+
+/// Moves a document, identified by id, into another category
+#[tauri::command]
+pub async fn move_document_by_id(
+    project_path: String,
+    document_id: String,
+    category: String,
+    subcategory: Option<String>,
+) -> Result<Document, String> {
+    let project = PathBuf::from(project_path);
+    ensure_project_writable(&project)?;
+
+    document_service::move_document_by_id(&project, &document_id, &category, subcategory.as_deref())
+        .map_err(|e| format!("Failed to move document: {}", e))
+}
+
+/// Rewrites a document's frontmatter keys in canonical order
+#[tauri::command]
+pub async fn normalize_frontmatter(document_path: String) -> Result<Document, String> {
+    let path = PathBuf::from(document_path);
+
+    document_service::normalize_frontmatter(&path)
+        .map_err(|e| format!("Failed to normalize frontmatter: {}", e))
+}
+
+/// Rewrites the frontmatter of every document in a project in canonical order
+#[tauri::command]
+pub async fn normalize_project_frontmatter(project_path: String) -> Result<usize, String> {
+    let path = PathBuf::from(project_path);
+    ensure_project_writable(&path)?;
+
+    document_service::normalize_project_frontmatter(&path)
+        .map_err(|e| format!("Failed to normalize frontmatter: {}", e))
+}
+
+/// Sets a document's status
+#[tauri::command]
+pub async fn set_document_status(document_path: String, status: String) -> Result<Document, String> {
+    let path = PathBuf::from(document_path);
+
+    document_service::set_document_status(&path, &status)
+        .map_err(|e| format!("Failed to set document status: {}", e))
+}
+
+/// Promotes a NARRATIVE draft to Final; anything else is a no-op with a message
+#[tauri::command]
+pub async fn promote_document(document_path: String, project_path: String) -> Result<PromoteOutcome, String> {
+    let path = PathBuf::from(document_path);
+    let project = PathBuf::from(project_path);
+    ensure_project_writable(&project)?;
+
+    document_service::promote_document(&path, &project)
+        .map_err(|e| format!("Failed to promote document: {}", e))
+}
+
 /// Lists all documents in a specific directory
 #[tauri::command]
 pub async fn list_documents_in_dir(dir_path: String) -> Result<Vec<Document>, String> {
@@ -61,11 +169,92 @@ pub async fn list_documents_in_dir(dir_path: String) -> Result<Vec<Document>, St
         .map_err(|e| format!("Failed to list documents: {}", e))
 }
 
+/// Lists cached metadata for the documents in a directory, sorted
+#[tauri::command]
+pub async fn list_document_meta(dir_path: String, sort: Option<String>) -> Result<Vec<DocumentMeta>, String> {
+    let path = PathBuf::from(dir_path);
+
+    // Parse sort order
+    let sort = match sort.as_deref().unwrap_or("modified") {
+        "modified" => DocumentSort::Modified,
+        "title" => DocumentSort::Title,
+        "wordCount" => DocumentSort::WordCount,
+        "order" => DocumentSort::Order,
+        "status" => DocumentSort::Status,
+        other => return Err(format!("Invalid sort order: {}", other)),
+    };
+
+    meta_cache_service::list_document_meta(&path, sort)
+        .map_err(|e| format!("Failed to list document metadata: {}", e))
+}
+
 /// Lists all documents in the project
 #[tauri::command]
 pub async fn list_all_documents(project_path: String) -> Result<Vec<Document>, String> {
     let path = PathBuf::from(project_path);
+    ensure_project_available(&path)?;
 
     document_service::list_all_documents(&path)
         .map_err(|e| format!("Failed to list all documents: {}", e))
 }
+
+/// Lists the most recently modified documents in the project
+#[tauri::command]
+pub async fn get_recent_documents(project_path: String, limit: usize) -> Result<Vec<Document>, String> {
+    let path = PathBuf::from(project_path);
+    ensure_project_available(&path)?;
+
+    document_service::recent_documents(&path, limit)
+        .map_err(|e| format!("Failed to get recent documents: {}", e))
+}
+
+/// Lists the frontmatter keys used across the project
+#[tauri::command]
+pub async fn get_frontmatter_keys(project_path: String) -> Result<Vec<KeyInfo>, String> {
+    let path = PathBuf::from(project_path);
+    ensure_project_available(&path)?;
+
+    document_service::frontmatter_keys(&path)
+        .map_err(|e| format!("Failed to get frontmatter keys: {}", e))
+}
+
+/// Flags overly long sentences and paragraphs in a document
+#[tauri::command]
+pub async fn get_prose_warnings(
+    document_path: String,
+    sentence_word_limit: Option<usize>,
+    paragraph_word_limit: Option<usize>,
+) -> Result<Vec<ProseWarning>, String> {
+    let path = PathBuf::from(document_path);
+    let sentence_limit = sentence_word_limit.unwrap_or(prose_service::DEFAULT_SENTENCE_WORD_LIMIT);
+    let paragraph_limit = paragraph_word_limit.unwrap_or(prose_service::DEFAULT_PARAGRAPH_WORD_LIMIT);
+
+    prose_service::document_prose_warnings(&path, sentence_limit, paragraph_limit)
+        .map_err(|e| format!("Failed to get prose warnings: {}", e))
+}
+
+/// Counts the scenes in a document, split on scene-break lines
+#[tauri::command]
+pub async fn get_scene_count(document_path: String, break_markers: Option<Vec<String>>) -> Result<usize, String> {
+    let path = PathBuf::from(document_path);
+    let markers: Vec<&str> = match &break_markers {
+        Some(markers) => markers.iter().map(|m| m.trim()).filter(|m| !m.is_empty()).collect(),
+        None => prose_service::DEFAULT_SCENE_BREAK_MARKERS.to_vec(),
+    };
+
+    prose_service::document_scene_count(&path, &markers)
+        .map_err(|e| format!("Failed to count scenes: {}", e))
+}
+
+/// Groups documents with duplicate bodies, optionally including near-duplicates
+#[tauri::command]
+pub async fn find_duplicate_documents(
+    project_path: String,
+    similarity_threshold: Option<f64>,
+) -> Result<Vec<DuplicateSet>, String> {
+    let path = PathBuf::from(project_path);
+    ensure_project_available(&path)?;
+
+    duplicate_service::find_duplicate_documents(&path, similarity_threshold)
+        .map_err(|e| format!("Failed to find duplicate documents: {}", e))
+}
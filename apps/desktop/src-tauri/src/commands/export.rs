@@ -0,0 +1,71 @@
+use crate::models::{CharacterReport, ExportFormat};
+use crate::services::{character_service, export_service};
+use super::ensure_project_available;
+use std::path::PathBuf;
+
+/// Parses an export format name sent by the frontend. Only Markdown and
+/// HTML have backends; anything else is rejected.
+fn parse_export_format(format: &str) -> Result<ExportFormat, String> {
+    match format {
+        "markdown" | "md" => Ok(ExportFormat::Markdown),
+        "html" => Ok(ExportFormat::Html),
+        _ => Err(format!("Invalid export format (expected markdown or html): {}", format)),
+    }
+}
+
+/// Exports the project's manuscript to a file
+#[tauri::command]
+pub async fn export_manuscript(
+    project_path: String,
+    output_path: String,
+    format: String, // "markdown" or "html"
+    resolve_links: Option<bool>,
+) -> Result<String, String> {
+    let path = PathBuf::from(project_path);
+    ensure_project_available(&path)?;
+    let output = PathBuf::from(output_path);
+    let format = parse_export_format(&format)?;
+
+    export_service::export_manuscript(&path, &output, format, resolve_links.unwrap_or(false))
+        .map(|p| p.to_string_lossy().to_string())
+        .map_err(|e| format!("Failed to export manuscript: {}", e))
+}
+
+/// Exports a single document to a file
+#[tauri::command]
+pub async fn export_document(
+    document_path: String,
+    output_path: String,
+    format: String, // "markdown" or "html"
+) -> Result<String, String> {
+    let path = PathBuf::from(document_path);
+    let output = PathBuf::from(output_path);
+    let format = parse_export_format(&format)?;
+
+    export_service::export_document(&path, &output, format)
+        .map(|p| p.to_string_lossy().to_string())
+        .map_err(|e| format!("Failed to export document: {}", e))
+}
+
+/// Exports a Markdown outline of the NARRATIVE documents
+#[tauri::command]
+pub async fn export_outline(project_path: String, output_path: String) -> Result<String, String> {
+    let path = PathBuf::from(project_path);
+    ensure_project_available(&path)?;
+    let output = PathBuf::from(output_path);
+
+    export_service::export_outline(&path, &output)
+        .map(|p| p.to_string_lossy().to_string())
+        .map_err(|e| format!("Failed to export outline: {}", e))
+}
+
+/// Lists every narrative document a character appears in, in reading order
+#[tauri::command]
+pub async fn get_character_report(project_path: String, character_path: String) -> Result<CharacterReport, String> {
+    let path = PathBuf::from(project_path);
+    ensure_project_available(&path)?;
+    let character = PathBuf::from(character_path);
+
+    character_service::character_report(&path, &character)
+        .map_err(|e| format!("Failed to build character report: {}", e))
+}
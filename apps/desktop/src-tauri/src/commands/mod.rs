@@ -1,8 +1,48 @@
 // Tauri IPC Commands
 // All frontend-callable functions are defined here
 
+use std::path::Path;
+
+use crate::services::file_service;
+
 pub mod projects;
 pub mod documents;
+pub mod export;
+pub mod archive;
+pub mod settings;
+
+/// Error code returned when a project folder is missing or not writable
+pub const PROJECT_UNAVAILABLE: &str = "PROJECT_UNAVAILABLE";
+
+/// Cheap check run before project commands so an unmounted drive produces a
+/// clean error instead of a failure deep in a service. Only looks at the
+/// folder's metadata, so it is safe for read-only commands and projects.
+pub fn ensure_project_available(project_path: &Path) -> Result<(), String> {
+    match std::fs::metadata(project_path) {
+        Ok(metadata) if metadata.is_dir() => Ok(()),
+        _ => Err(format!(
+            "{}: Project folder not found: {}",
+            PROJECT_UNAVAILABLE,
+            project_path.display()
+        )),
+    }
+}
+
+/// Check run before commands that modify a project: on top of
+/// `ensure_project_available`, probes that the folder can be written to so
+/// revoked permissions fail up front
+pub fn ensure_project_writable(project_path: &Path) -> Result<(), String> {
+    ensure_project_available(project_path)?;
+
+    if !file_service::is_writable(project_path) {
+        return Err(format!(
+            "{}: Project folder is not writable: {}",
+            PROJECT_UNAVAILABLE,
+            project_path.display()
+        ));
+    }
+    Ok(())
+}
 
 /// Example greeting command
 #[tauri::command]
@@ -13,3 +53,40 @@ pub fn greet(name: &str) -> String {
 // Future command modules will be added here:
 // pub mod search;
 // pub mod ai;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[tokio::test]
+    async fn test_missing_project_returns_project_unavailable() {
+        let missing = env::temp_dir().join("aycd_commands_test_missing_project");
+        let missing_path = missing.to_string_lossy().to_string();
+
+        let error = documents::list_all_documents(missing_path.clone()).await.unwrap_err();
+        assert!(error.starts_with(PROJECT_UNAVAILABLE), "{}", error);
+
+        let error = projects::open_project(missing_path).await.unwrap_err();
+        assert!(error.starts_with(PROJECT_UNAVAILABLE), "{}", error);
+
+        assert!(ensure_project_available(&env::temp_dir()).is_ok());
+        assert!(ensure_project_writable(&env::temp_dir()).is_ok());
+        let error = ensure_project_writable(&missing).unwrap_err();
+        assert!(error.starts_with(PROJECT_UNAVAILABLE), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn test_export_rejects_formats_without_a_backend() {
+        let temp_dir = env::temp_dir().join("aycd_commands_test_export_formats");
+        let output = temp_dir.join("out.pdf").to_string_lossy().to_string();
+        let document = temp_dir.join("Scene.md").to_string_lossy().to_string();
+
+        for format in ["pdf", "docx"] {
+            let error = export::export_document(document.clone(), output.clone(), format.to_string())
+                .await
+                .unwrap_err();
+            assert!(error.starts_with("Invalid export format"), "{}", error);
+        }
+    }
+}
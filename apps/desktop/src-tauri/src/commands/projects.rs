@@ -1,6 +1,8 @@
-use crate::models::Project;
-use crate::services::project_service;
+use crate::models::{Activity, GoalProgress, MergeReport, Project, ProjectRenamed, ProjectStats};
+use crate::services::{activity_service, project_service, settings_service, stats_service};
+use super::{ensure_project_available, ensure_project_writable};
 use std::path::PathBuf;
+use tauri::{AppHandle, Emitter};
 
 /// Creates a new AYCD project
 #[tauri::command]
@@ -18,9 +20,66 @@ pub async fn create_project(name: String, custom_path: Option<String>) -> Result
 #[tauri::command]
 pub async fn open_project(project_path: String) -> Result<Project, String> {
     let path = PathBuf::from(project_path);
+    ensure_project_available(&path)?;
 
-    project_service::open_project(&path)
-        .map_err(|e| format!("Failed to open project: {}", e))
+    let project = project_service::open_project(&path)
+        .map_err(|e| format!("Failed to open project: {}", e))?;
+
+    if let Err(e) = settings_service::record_recent_project(&path) {
+        eprintln!("Failed to record recent project {}: {}", path.display(), e);
+    }
+
+    Ok(project)
+}
+
+/// Renames a project, moving its folder to match the new name. When the
+/// folder moves, a `project-renamed` event carries the old and new paths so
+/// open watchers can be re-pointed.
+#[tauri::command]
+pub async fn rename_project(app: AppHandle, project_path: String, new_name: String) -> Result<Project, String> {
+    let path = PathBuf::from(&project_path);
+    ensure_project_writable(&path)?;
+
+    let project = project_service::rename_project(&path, &new_name)
+        .map_err(|e| format!("Failed to rename project: {}", e))?;
+
+    if project.path != project_path {
+        let renamed = ProjectRenamed {
+            old_path: project_path,
+            new_path: project.path.clone(),
+        };
+        if let Err(e) = app.emit("project-renamed", renamed) {
+            eprintln!("Failed to emit project-renamed for {}: {}", project.path, e);
+        }
+    }
+
+    Ok(project)
+}
+
+/// Rebuilds a missing or corrupted project.json
+#[tauri::command]
+pub async fn recover_project(project_path: String, name: String) -> Result<Project, String> {
+    let path = PathBuf::from(project_path);
+    ensure_project_writable(&path)?;
+
+    project_service::recover_project(&path, &name)
+        .map_err(|e| format!("Failed to recover project: {}", e))
+}
+
+/// Merges one project's documents into another
+#[tauri::command]
+pub async fn merge_project(
+    source_path: String,
+    dest_path: String,
+    category_prefix: Option<String>,
+) -> Result<MergeReport, String> {
+    let source = PathBuf::from(source_path);
+    let dest = PathBuf::from(dest_path);
+    ensure_project_available(&source)?;
+    ensure_project_writable(&dest)?;
+
+    project_service::merge_project(&source, &dest, category_prefix.as_deref())
+        .map_err(|e| format!("Failed to merge projects: {}", e))
 }
 
 /// Lists all projects in the default directory
@@ -44,3 +103,60 @@ pub async fn update_project(project: Project) -> Result<(), String> {
     project_service::update_project(&project)
         .map_err(|e| format!("Failed to update project: {}", e))
 }
+
+/// Sets or clears the word-count goal of a category (e.g. "NARRATIVE/Drafts")
+#[tauri::command]
+pub async fn set_category_goal(
+    project_path: String,
+    category: String,
+    target: Option<usize>,
+) -> Result<Project, String> {
+    let path = PathBuf::from(project_path);
+    ensure_project_writable(&path)?;
+
+    stats_service::set_category_goal(&path, &category, target)
+        .map_err(|e| format!("Failed to set category goal: {}", e))
+}
+
+/// Gets progress towards a category's word-count goal
+#[tauri::command]
+pub async fn get_category_goal_progress(
+    project_path: String,
+    category: String,
+) -> Result<GoalProgress, String> {
+    let path = PathBuf::from(project_path);
+    ensure_project_available(&path)?;
+
+    stats_service::category_goal_progress(&path, &category)
+        .map_err(|e| format!("Failed to get category goal progress: {}", e))
+}
+
+/// Gets the project's word and document totals from the stats cache
+#[tauri::command]
+pub async fn get_project_stats(project_path: String) -> Result<ProjectStats, String> {
+    let path = PathBuf::from(project_path);
+    ensure_project_available(&path)?;
+
+    stats_service::project_stats(&path)
+        .map_err(|e| format!("Failed to get project stats: {}", e))
+}
+
+/// Rebuilds the stats cache from every document in the project
+#[tauri::command]
+pub async fn recompute_stats(project_path: String) -> Result<ProjectStats, String> {
+    let path = PathBuf::from(project_path);
+    ensure_project_writable(&path)?;
+
+    stats_service::recompute_stats(&path)
+        .map_err(|e| format!("Failed to recompute project stats: {}", e))
+}
+
+/// Gets the project's most recent file operations, newest first
+#[tauri::command]
+pub async fn get_project_activity(project_path: String, limit: Option<usize>) -> Result<Vec<Activity>, String> {
+    let path = PathBuf::from(project_path);
+    ensure_project_available(&path)?;
+
+    activity_service::project_activity(&path, limit.unwrap_or(50))
+        .map_err(|e| format!("Failed to get project activity: {}", e))
+}
@@ -0,0 +1,25 @@
+use crate::models::{AppConfig, PathInfo};
+use crate::services::{document_service, settings_service};
+
+/// Gets the app-wide config
+#[tauri::command]
+pub async fn get_app_config() -> Result<AppConfig, String> {
+    settings_service::load_app_config()
+        .map_err(|e| format!("Failed to load app config: {}", e))
+}
+
+/// Saves the app-wide config
+#[tauri::command]
+pub async fn update_app_config(config: AppConfig) -> Result<(), String> {
+    settings_service::save_app_config(&config)
+        .map_err(|e| format!("Failed to save app config: {}", e))?;
+
+    document_service::set_metrics_interval(config.metrics_interval_ms)
+        .map_err(|e| format!("Failed to apply app config: {}", e))
+}
+
+/// Reports where the app's files live, for the troubleshooting screen
+#[tauri::command]
+pub async fn get_paths() -> Result<PathInfo, String> {
+    Ok(settings_service::get_paths())
+}
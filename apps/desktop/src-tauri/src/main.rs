@@ -16,15 +16,49 @@ fn main() {
             greet,
             commands::projects::create_project,
             commands::projects::open_project,
+            commands::projects::rename_project,
+            commands::projects::recover_project,
+            commands::projects::merge_project,
             commands::projects::list_projects,
             commands::projects::get_projects_root,
             commands::projects::update_project,
+            commands::projects::set_category_goal,
+            commands::projects::get_category_goal_progress,
+            commands::projects::get_project_stats,
+            commands::projects::recompute_stats,
+            commands::projects::get_project_activity,
             commands::documents::create_document,
             commands::documents::read_document,
             commands::documents::update_document,
+            commands::documents::quick_save_document,
+            commands::documents::replace_in_document,
+            commands::documents::touch_document,
             commands::documents::delete_document,
+            commands::documents::move_document,
+            commands::documents::move_document_by_id,
+            commands::documents::set_document_status,
+            commands::documents::normalize_frontmatter,
+            commands::documents::normalize_project_frontmatter,
+            commands::documents::promote_document,
             commands::documents::list_documents_in_dir,
+            commands::documents::list_document_meta,
             commands::documents::list_all_documents,
+            commands::documents::get_recent_documents,
+            commands::documents::get_frontmatter_keys,
+            commands::documents::get_prose_warnings,
+            commands::documents::get_scene_count,
+            commands::documents::find_duplicate_documents,
+            commands::export::export_manuscript,
+            commands::export::export_document,
+            commands::export::export_outline,
+            commands::export::get_character_report,
+            commands::archive::archive_project,
+            commands::archive::open_archive_readonly,
+            commands::archive::list_archive_documents,
+            commands::archive::read_archive_document,
+            commands::settings::get_app_config,
+            commands::settings::update_app_config,
+            commands::settings::get_paths,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
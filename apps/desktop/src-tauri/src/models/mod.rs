@@ -2,6 +2,7 @@
 // Rust representations of core domain entities
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -13,9 +14,44 @@ pub struct Project {
     pub created_at: i64,
     #[serde(alias = "modified_at")]
     pub modified_at: i64,
+    #[serde(default)]
+    pub settings: ProjectConfig,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Per-project settings stored in project.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectConfig {
+    /// Word-count targets keyed by category path, e.g. "NARRATIVE/Drafts"
+    #[serde(default, alias = "category_goals", skip_serializing_if = "HashMap::is_empty")]
+    pub category_goals: HashMap<String, usize>,
+    /// Overrides the app-wide default author for this project
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Frontend-owned settings (theme, default view, ...) preserved as-is
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// App-wide preferences stored in ~/AYCD/config.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppConfig {
+    /// Default author written into new documents and export title pages
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Minimum time between word-count recomputations on quick saves
+    #[serde(default, alias = "metrics_interval_ms", skip_serializing_if = "Option::is_none")]
+    pub metrics_interval_ms: Option<u64>,
+    /// Recently opened project folders, most recent first
+    #[serde(default, alias = "recent_projects", skip_serializing_if = "Vec::is_empty")]
+    pub recent_projects: Vec<String>,
+    /// Preferences owned by the frontend (editor, AI providers, ...) preserved as-is
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum DocumentType {
     World,
@@ -41,6 +77,239 @@ pub struct Document {
     pub modified_at: i64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<serde_json::Value>,
+    /// Set when `word_count` is from an earlier save and will be refreshed soon
+    #[serde(default, alias = "metrics_stale", skip_serializing_if = "std::ops::Not::not")]
+    pub metrics_stale: bool,
+}
+
+/// A frontmatter key observed across a project's documents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyInfo {
+    pub key: String,
+    pub count: usize,
+    #[serde(alias = "sample_values")]
+    pub sample_values: Vec<String>,
+}
+
+/// Output formats supported by exports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Markdown,
+    Html,
+}
+
+/// Progress towards a word-count goal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalProgress {
+    pub target: usize,
+    pub current: usize,
+    pub percentage: f64,
+}
+
+/// Project-wide word and document totals
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectStats {
+    #[serde(alias = "total_words")]
+    pub total_words: usize,
+    #[serde(alias = "world_words")]
+    pub world_words: usize,
+    #[serde(alias = "narrative_words")]
+    pub narrative_words: usize,
+    #[serde(alias = "document_count")]
+    pub document_count: usize,
+}
+
+/// What a prose warning refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProseWarningKind {
+    Sentence,
+    Paragraph,
+}
+
+/// A sentence or paragraph exceeding its word limit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProseWarning {
+    pub kind: ProseWarningKind,
+    /// 1-based, inclusive line range
+    #[serde(alias = "start_line")]
+    pub start_line: usize,
+    #[serde(alias = "end_line")]
+    pub end_line: usize,
+    #[serde(alias = "word_count")]
+    pub word_count: usize,
+}
+
+/// Result of promoting a document. Documents that aren't NARRATIVE drafts
+/// are left as they are, with `message` saying why.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromoteOutcome {
+    pub document: Document,
+    pub promoted: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Summary of a project merge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeReport {
+    #[serde(alias = "documents_merged")]
+    pub documents_merged: usize,
+    #[serde(alias = "links_rewritten")]
+    pub links_rewritten: usize,
+}
+
+/// Payload of the `project-renamed` event, sent when a rename moved the
+/// project folder so open watchers can follow it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectRenamed {
+    #[serde(alias = "old_path")]
+    pub old_path: String,
+    #[serde(alias = "new_path")]
+    pub new_path: String,
+}
+
+/// A group of documents with identical or near-identical bodies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateSet {
+    pub paths: Vec<String>,
+    /// 1.0 for exact duplicates, otherwise the lowest similarity between any
+    /// two members (which can be below the threshold, as sets are chained)
+    pub similarity: f64,
+    pub exact: bool,
+}
+
+/// Kind of file operation recorded in the activity log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActivityKind {
+    Create,
+    Update,
+    Delete,
+    Move,
+}
+
+/// An entry of a project's activity log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Activity {
+    pub timestamp: i64,
+    pub kind: ActivityKind,
+    pub path: String,
+    /// Previous location, for moves
+    #[serde(default, alias = "previous_path", skip_serializing_if = "Option::is_none")]
+    pub previous_path: Option<String>,
+}
+
+/// Options for find/replace operations
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReplaceOptions {
+    /// Treat the search text as a regular expression (`$1` expands in replacements)
+    pub regex: bool,
+    #[serde(alias = "case_sensitive")]
+    pub case_sensitive: bool,
+    #[serde(alias = "whole_word")]
+    pub whole_word: bool,
+}
+
+/// Where a path points and whether it can be used
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathStatus {
+    pub path: String,
+    pub exists: bool,
+    pub writable: bool,
+}
+
+/// Resolved locations used by the app, for troubleshooting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathInfo {
+    /// `None` when the home directory can't be determined
+    #[serde(alias = "home_dir")]
+    pub home_dir: Option<PathStatus>,
+    #[serde(alias = "projects_root")]
+    pub projects_root: Option<PathStatus>,
+    #[serde(alias = "config_path")]
+    pub config_path: Option<PathStatus>,
+}
+
+/// Lightweight per-document values used for sorting and filtering without
+/// reading document bodies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentMeta {
+    pub path: String,
+    pub id: String,
+    pub title: String,
+    #[serde(alias = "document_type")]
+    pub document_type: DocumentType,
+    #[serde(alias = "word_count")]
+    pub word_count: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<i64>,
+    #[serde(alias = "modified_at")]
+    pub modified_at: i64,
+}
+
+/// Sort orders for document listings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DocumentSort {
+    Modified,
+    Title,
+    WordCount,
+    Order,
+    Status,
+}
+
+/// How a character shows up in a document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AppearanceKind {
+    Link,
+    Alias,
+    Mention,
+}
+
+/// One narrative document a character appears in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterAppearance {
+    pub path: String,
+    pub title: String,
+    /// Kinds of appearance found in the document, in order of first occurrence
+    pub kinds: Vec<AppearanceKind>,
+    pub occurrences: usize,
+    /// Text around the first few occurrences
+    pub snippets: Vec<String>,
+}
+
+/// Every narrative document a character appears in, in reading order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterReport {
+    #[serde(alias = "character_id")]
+    pub character_id: String,
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub appearances: Vec<CharacterAppearance>,
+    #[serde(default, alias = "first_appearance", skip_serializing_if = "Option::is_none")]
+    pub first_appearance: Option<String>,
+    #[serde(default, alias = "last_appearance", skip_serializing_if = "Option::is_none")]
+    pub last_appearance: Option<String>,
 }
 
 // Future models will be added here:
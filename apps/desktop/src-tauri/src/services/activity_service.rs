@@ -0,0 +1,182 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::models::{Activity, ActivityKind};
+use super::file_service::{ensure_dir, read_file, with_dir_lock, write_file};
+use super::project_service::find_project_root;
+
+/// Entries kept when the activity log is trimmed
+const MAX_ACTIVITY_ENTRIES: usize = 1000;
+
+/// Size past which the activity log is trimmed back to the newest
+/// `MAX_ACTIVITY_ENTRIES` entries
+const MAX_ACTIVITY_LOG_BYTES: u64 = 512 * 1024;
+
+/// Location of the activity log inside a project
+fn activity_log_path(project_path: &Path) -> PathBuf {
+    project_path.join("cache").join("activity.jsonl")
+}
+
+/// Appends an operation to the project's activity log
+pub fn record_activity(
+    project_path: &Path,
+    kind: ActivityKind,
+    path: &Path,
+    previous_path: Option<&Path>,
+) -> Result<()> {
+    let log_path = activity_log_path(project_path);
+    ensure_dir(&project_path.join("cache"))?;
+
+    let activity = Activity {
+        timestamp: Utc::now().timestamp(),
+        kind,
+        path: path.to_string_lossy().to_string(),
+        previous_path: previous_path.map(|p| p.to_string_lossy().to_string()),
+    };
+    let line = serde_json::to_string(&activity).context("Failed to serialize activity")?;
+
+    with_dir_lock(project_path, || {
+        append_activity_line(&log_path, &line, MAX_ACTIVITY_LOG_BYTES, MAX_ACTIVITY_ENTRIES)
+    })
+}
+
+/// Appends a line to the log, trimming it to its newest `keep` lines once it
+/// grows past `max_bytes`. The caller holds the project lock.
+fn append_activity_line(log_path: &Path, line: &str, max_bytes: u64, keep: usize) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path)
+        .with_context(|| format!("Failed to open activity log: {:?}", log_path))?;
+    writeln!(file, "{}", line).with_context(|| format!("Failed to write activity log: {:?}", log_path))?;
+
+    let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+    if size <= max_bytes {
+        return Ok(());
+    }
+
+    let content = read_file(log_path)?;
+    let lines: Vec<&str> = content.lines().collect();
+    let mut trimmed = lines[lines.len().saturating_sub(keep)..].join("\n");
+    trimmed.push('\n');
+    write_file(log_path, &trimmed)
+}
+
+/// Points activity entries recorded under a project's old folder at the
+/// folder it now lives in, after the project was moved or renamed
+pub fn rebase_activity(project_path: &Path, old_project_path: &Path) -> Result<()> {
+    let log_path = activity_log_path(project_path);
+    if !log_path.exists() {
+        return Ok(());
+    }
+
+    let rebase = |path: &str| match Path::new(path).strip_prefix(old_project_path) {
+        Ok(relative) => project_path.join(relative).to_string_lossy().to_string(),
+        Err(_) => path.to_string(),
+    };
+
+    with_dir_lock(project_path, || {
+        let mut rewritten = String::new();
+        for line in read_file(&log_path)?.lines() {
+            // Unparseable lines are kept as they are
+            let Ok(mut activity) = serde_json::from_str::<Activity>(line) else {
+                rewritten.push_str(line);
+                rewritten.push('\n');
+                continue;
+            };
+            activity.path = rebase(&activity.path);
+            activity.previous_path = activity.previous_path.as_deref().map(rebase);
+            rewritten.push_str(&serde_json::to_string(&activity).context("Failed to serialize activity")?);
+            rewritten.push('\n');
+        }
+        write_file(&log_path, &rewritten)
+    })
+}
+
+/// Records an operation on a document in the project containing it.
+/// Logging is best-effort and never fails the operation itself.
+pub fn log_document_activity(kind: ActivityKind, path: &Path, previous_path: Option<&Path>) {
+    let Some(project_path) = find_project_root(path) else {
+        return;
+    };
+
+    if let Err(e) = record_activity(&project_path, kind, path, previous_path) {
+        eprintln!("Failed to record activity for {}: {}", path.display(), e);
+    }
+}
+
+/// Returns the most recent operations, newest first
+pub fn project_activity(project_path: &Path, limit: usize) -> Result<Vec<Activity>> {
+    let log_path = activity_log_path(project_path);
+    if !log_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = read_file(&log_path)?;
+    let activities = content
+        .lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str::<Activity>(line).ok())
+        .take(limit)
+        .collect();
+
+    Ok(activities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DocumentType;
+    use crate::services::document_service::{create_document, delete_document};
+    use crate::services::project_service::create_project;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn test_create_then_delete_is_logged_in_order() {
+        let temp_dir = env::temp_dir().join("aycd_activity_test");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let project = create_project("novel", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+
+        let doc = create_document(&project_path, "Anna", DocumentType::World, "Cast", None).unwrap();
+        delete_document(Path::new(&doc.path)).unwrap();
+
+        let activity = project_activity(&project_path, 10).unwrap();
+        assert_eq!(activity.len(), 2);
+        assert_eq!(activity[0].kind, ActivityKind::Delete);
+        assert_eq!(activity[1].kind, ActivityKind::Create);
+        assert!(activity.iter().all(|a| a.path == doc.path));
+        assert!(activity[0].timestamp >= activity[1].timestamp);
+
+        assert_eq!(project_activity(&project_path, 1).unwrap().len(), 1);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_activity_log_is_trimmed_to_newest_entries() {
+        let temp_dir = env::temp_dir().join("aycd_activity_test_trim");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+        let log_path = temp_dir.join("activity.jsonl");
+
+        for i in 0..50 {
+            append_activity_line(&log_path, &format!("entry {:02}", i), 100, 5).unwrap();
+        }
+
+        // Every trim keeps the newest 5, so the log never holds much more
+        // than fits in 100 bytes
+        let content = fs::read_to_string(&log_path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert!(lines.len() <= 12, "{} lines", lines.len());
+        assert_eq!(lines.last(), Some(&"entry 49"));
+        assert!(lines.windows(2).all(|pair| pair[0] < pair[1]));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+}
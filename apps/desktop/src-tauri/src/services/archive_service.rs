@@ -0,0 +1,279 @@
+use anyhow::{Context, Result};
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use crate::models::{Document, Project};
+use super::document_service::parse_document;
+use super::file_service::ensure_dir;
+
+/// Separates the archive file from the entry inside it in archive document
+/// paths, e.g. `/backups/novel.zip!/WORLD/Cast/Anna.md`
+pub const ARCHIVE_PATH_SEPARATOR: &str = "!/";
+
+/// Project folders that are rebuilt on demand and left out of archives
+const SKIPPED_DIRS: [&str; 2] = ["cache", "search"];
+
+/// Splits an archive document path into the zip file and the entry name
+fn split_archive_path(path: &Path) -> Option<(PathBuf, String)> {
+    let path = path.to_string_lossy();
+    let (zip_path, entry) = path.rsplit_once(ARCHIVE_PATH_SEPARATOR)?;
+    Some((PathBuf::from(zip_path), entry.to_string()))
+}
+
+/// Returns true for paths pointing inside an archive
+pub fn is_archive_path(path: &Path) -> bool {
+    split_archive_path(path).is_some_and(|(zip_path, _)| zip_path.is_file())
+}
+
+/// Archives are read-only: refuses any write to a path inside one
+pub fn ensure_not_archived(path: &Path) -> Result<()> {
+    if is_archive_path(path) {
+        anyhow::bail!("Archived projects are read-only: {}", path.display());
+    }
+    Ok(())
+}
+
+/// Zips a project folder (without its cache and search index)
+pub fn archive_project(project_path: &Path, output: &Path) -> Result<PathBuf> {
+    if !project_path.join("project.json").is_file() {
+        anyhow::bail!("Not a valid AYCD project: project.json not found");
+    }
+    // The archive would otherwise end up zipping itself
+    if resolve_path(output).starts_with(project_path.canonicalize()?) {
+        anyhow::bail!("Archive cannot be written inside the project folder: {}", output.display());
+    }
+    if let Some(parent) = output.parent() {
+        ensure_dir(parent)?;
+    }
+
+    let file = File::create(output).with_context(|| format!("Failed to create archive: {:?}", output))?;
+    let mut zip = ZipWriter::new(file);
+    add_dir_to_zip(&mut zip, project_path, project_path)?;
+    zip.finish().context("Failed to finish archive")?;
+
+    Ok(output.to_path_buf())
+}
+
+/// Canonicalizes a path that may not exist yet through its nearest existing
+/// ancestor, folding `..` in the missing part
+fn resolve_path(path: &Path) -> PathBuf {
+    path.ancestors()
+        .find_map(|ancestor| {
+            let rest = path.strip_prefix(ancestor).ok()?;
+            let mut resolved = ancestor.canonicalize().ok()?;
+            for component in rest.components() {
+                match component {
+                    Component::ParentDir => {
+                        resolved.pop();
+                    }
+                    Component::CurDir => {}
+                    other => resolved.push(other),
+                }
+            }
+            Some(resolved)
+        })
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Recursively adds a directory's files to an archive with `/`-separated
+/// names. Symlinks are skipped: following them could pull in files from
+/// outside the project or loop forever.
+fn add_dir_to_zip(zip: &mut ZipWriter<File>, root: &Path, dir: &Path) -> Result<()> {
+    let options = SimpleFileOptions::default();
+
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read directory: {:?}", dir))? {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            continue;
+        }
+        let relative = path.strip_prefix(root)?;
+        let name = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        if file_type.is_dir() {
+            if dir == root && SKIPPED_DIRS.contains(&name.as_str()) {
+                continue;
+            }
+            zip.add_directory(format!("{}/", name), options)?;
+            add_dir_to_zip(zip, root, &path)?;
+        } else {
+            zip.start_file(name, options)?;
+            zip.write_all(&fs::read(&path).with_context(|| format!("Failed to read file: {:?}", path))?)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Opens an archive for reading
+fn open_zip(zip_path: &Path) -> Result<ZipArchive<File>> {
+    let file = File::open(zip_path).with_context(|| format!("Failed to open archive: {:?}", zip_path))?;
+    ZipArchive::new(file).with_context(|| format!("Not a valid archive: {:?}", zip_path))
+}
+
+/// Reads an archive entry as text
+fn read_entry(archive: &mut ZipArchive<File>, name: &str) -> Result<String> {
+    let mut entry = archive
+        .by_name(name)
+        .with_context(|| format!("Entry not found in archive: {}", name))?;
+    let mut content = String::new();
+    entry
+        .read_to_string(&mut content)
+        .with_context(|| format!("Failed to read archive entry: {}", name))?;
+    Ok(content)
+}
+
+/// Opens an archived project for read-only browsing, without extracting it.
+/// The returned project's path is the archive itself.
+pub fn open_archive_readonly(zip_path: &Path) -> Result<Project> {
+    let mut archive = open_zip(zip_path)?;
+    let content = read_entry(&mut archive, "project.json")
+        .context("Not a valid AYCD archive: project.json not found")?;
+
+    let mut project: Project = serde_json::from_str(&content).context("Failed to parse project.json")?;
+    project.path = zip_path.to_string_lossy().to_string();
+    Ok(project)
+}
+
+/// Lists the documents stored in an archive
+pub fn list_archive_documents(zip_path: &Path) -> Result<Vec<Document>> {
+    let mut archive = open_zip(zip_path)?;
+    let metadata = fs::metadata(zip_path)?;
+
+    let names: Vec<String> = archive
+        .file_names()
+        .filter(|name| (name.starts_with("WORLD/") || name.starts_with("NARRATIVE/")) && name.ends_with(".md"))
+        .map(str::to_string)
+        .collect();
+
+    let mut documents = Vec::with_capacity(names.len());
+    for name in names {
+        let content = read_entry(&mut archive, &name)?;
+        let path = PathBuf::from(format!("{}{}{}", zip_path.display(), ARCHIVE_PATH_SEPARATOR, name));
+        documents.push(parse_document(&path, content, &metadata, None));
+    }
+
+    documents.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(documents)
+}
+
+/// Reads a single document from an archive document path
+pub fn read_archive_document(document_path: &Path) -> Result<Document> {
+    let (zip_path, name) = split_archive_path(document_path)
+        .ok_or_else(|| anyhow::anyhow!("Not an archive document path: {}", document_path.display()))?;
+
+    let mut archive = open_zip(&zip_path)?;
+    let metadata = fs::metadata(&zip_path)?;
+    let content = read_entry(&mut archive, &name)?;
+
+    Ok(parse_document(document_path, content, &metadata, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DocumentType;
+    use crate::services::document_service::{create_document, update_document};
+    use crate::services::project_service::create_project;
+    use std::env;
+
+    #[test]
+    fn test_browse_archive_without_extracting() {
+        let temp_dir = env::temp_dir().join("aycd_archive_test");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let project = create_project("novel", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+        let anna = create_document(&project_path, "Anna", DocumentType::World, "Cast", None).unwrap();
+        let chapter = create_document(&project_path, "Chapter", DocumentType::Narrative, "Drafts", None).unwrap();
+        fs::write(&chapter.path, "---\ntitle: Chapter\n---\n\nIt begins.").unwrap();
+
+        let zip_path = temp_dir.join("backups/novel.zip");
+        archive_project(&project_path, &zip_path).unwrap();
+        let entries_before = fs::read_dir(temp_dir.join("backups")).unwrap().count();
+
+        let archived = open_archive_readonly(&zip_path).unwrap();
+        assert_eq!(archived.id, project.id);
+        assert_eq!(PathBuf::from(&archived.path), zip_path);
+
+        let documents = list_archive_documents(&zip_path).unwrap();
+        assert_eq!(documents.len(), 2);
+        let archived_anna = documents.iter().find(|d| d.title == "Anna").unwrap();
+        assert_eq!(archived_anna.id, anna.id);
+        assert_eq!(archived_anna.document_type, DocumentType::World);
+
+        let chapter_path = documents.iter().find(|d| d.title == "Chapter").unwrap().path.clone();
+        let read = read_archive_document(Path::new(&chapter_path)).unwrap();
+        assert_eq!(read.content, "---\ntitle: Chapter\n---\n\nIt begins.");
+        assert_eq!(read.word_count, 2);
+
+        // Writes into the archive are refused and nothing is extracted
+        let error = update_document(Path::new(&chapter_path), "changed").unwrap_err();
+        assert!(error.to_string().contains("read-only"));
+        assert_eq!(fs::read_dir(temp_dir.join("backups")).unwrap().count(), entries_before);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_archive_output_inside_project_is_rejected() {
+        let temp_dir = env::temp_dir().join("aycd_archive_test_inside");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let project = create_project("novel", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+
+        for output in [
+            project_path.join("novel.zip"),
+            project_path.join("backups/novel.zip"),
+            temp_dir.join("other/../novel/backups/novel.zip"),
+        ] {
+            let error = archive_project(&project_path, &output).unwrap_err();
+            assert!(error.to_string().contains("inside the project folder"));
+        }
+        assert!(!project_path.join("backups").exists());
+
+        // A sibling folder whose name merely starts with the project's is fine
+        let sibling = temp_dir.join("novel-backups/novel.zip");
+        archive_project(&project_path, &sibling).unwrap();
+        assert!(sibling.is_file());
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_archive_skips_symlinks() {
+        let temp_dir = env::temp_dir().join("aycd_archive_test_symlinks");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let project = create_project("novel", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+        let outside = temp_dir.join("outside");
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(outside.join("secret.md"), "not part of the project").unwrap();
+
+        // A link out of the project and a link back to its own root
+        std::os::unix::fs::symlink(&outside, project_path.join("WORLD/Linked")).unwrap();
+        std::os::unix::fs::symlink(&project_path, project_path.join("NARRATIVE/Loop")).unwrap();
+        std::os::unix::fs::symlink(outside.join("secret.md"), project_path.join("WORLD/Cast/Secret.md")).unwrap();
+
+        let zip_path = temp_dir.join("novel.zip");
+        archive_project(&project_path, &zip_path).unwrap();
+
+        let archive = open_zip(&zip_path).unwrap();
+        let names: Vec<&str> = archive.file_names().collect();
+        assert!(names.contains(&"project.json"));
+        assert!(names.iter().all(|name| !name.contains("Linked") && !name.contains("Loop") && !name.contains("Secret")));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+}
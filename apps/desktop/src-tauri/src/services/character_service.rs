@@ -0,0 +1,219 @@
+use anyhow::{Context, Result};
+use regex::{Regex, RegexBuilder};
+use std::fs;
+use std::path::Path;
+
+use crate::models::{AppearanceKind, CharacterAppearance, CharacterReport};
+use super::document_service::{read_document, strip_frontmatter};
+use super::export_service::{narrative_paths, readable_documents};
+use super::link_service::{build_id_map, find_wikilinks, resolve_wikilinks, IdMapEntry};
+
+/// Bytes of context kept on each side of an occurrence in a snippet
+const SNIPPET_CONTEXT: usize = 60;
+
+/// Snippets kept per document
+const MAX_SNIPPETS: usize = 3;
+
+/// Reads the `aliases` frontmatter value, written either as `Anna, Annie`
+/// or `[Anna, "Annie"]`
+fn parse_aliases(value: &str) -> Vec<String> {
+    value
+        .trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(',')
+        .map(|alias| alias.trim().trim_matches(['"', '\'']).trim().to_string())
+        .filter(|alias| !alias.is_empty())
+        .collect()
+}
+
+/// Builds a case-insensitive matcher for any of the names. Word boundaries
+/// are only required where a name starts or ends with a word character.
+fn names_regex(names: &[&str]) -> Result<Regex> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let alternatives: Vec<String> = names
+        .iter()
+        .map(|name| {
+            let start = if name.starts_with(is_word) { r"\b" } else { "" };
+            let end = if name.ends_with(is_word) { r"\b" } else { "" };
+            format!("{}{}{}", start, regex::escape(name), end)
+        })
+        .collect();
+
+    RegexBuilder::new(&format!("(?:{})", alternatives.join("|")))
+        .case_insensitive(true)
+        .build()
+        .context("Failed to build character name pattern")
+}
+
+/// Text around a byte range, on one line, with ellipses where it was cut
+fn snippet(text: &str, start: usize, end: usize) -> String {
+    let mut from = start.saturating_sub(SNIPPET_CONTEXT);
+    while !text.is_char_boundary(from) {
+        from -= 1;
+    }
+    let mut to = (end + SNIPPET_CONTEXT).min(text.len());
+    while !text.is_char_boundary(to) {
+        to += 1;
+    }
+
+    let excerpt = text[from..to].split_whitespace().collect::<Vec<_>>().join(" ");
+    format!(
+        "{}{}{}",
+        if from > 0 { "…" } else { "" },
+        excerpt,
+        if to < text.len() { "…" } else { "" }
+    )
+}
+
+/// Lists every narrative document a character appears in, in reading order.
+/// A document counts when it links to the character's document, or mentions
+/// its title or one of its `aliases` (case-insensitive, whole words).
+pub fn character_report(project_path: &Path, character_path: &Path) -> Result<CharacterReport> {
+    let character = read_document(character_path)?;
+    let aliases = character
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get("aliases"))
+        .and_then(|v| v.as_str())
+        .map(parse_aliases)
+        .unwrap_or_default();
+
+    let mut names: Vec<&str> = vec![character.title.as_str()];
+    names.extend(aliases.iter().map(String::as_str));
+    names.retain(|name| !name.trim().is_empty());
+    // Longest first so "Anna Vale" wins over "Anna"
+    names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+    let names_regex = names_regex(&names)?;
+    let title = character.title.to_lowercase();
+
+    let id_map = build_id_map(project_path)?;
+    // Match by id so any spelling of the character's path works; documents
+    // without an id fall back to comparing canonical paths
+    let character_file = fs::canonicalize(character_path).unwrap_or_else(|_| character_path.to_path_buf());
+    let is_character = |entry: &IdMapEntry| {
+        if character.id.is_empty() {
+            fs::canonicalize(&entry.path).is_ok_and(|path| path == character_file)
+        } else {
+            entry.id == character.id
+        }
+    };
+    let mut appearances = Vec::new();
+
+    let mut paths = narrative_paths(project_path)?;
+    paths.retain(|path| !fs::canonicalize(path).is_ok_and(|path| path == character_file));
+
+    // One unreadable scene shouldn't hide every other appearance
+    for document in readable_documents(&paths) {
+        let body = strip_frontmatter(&document.content);
+        let mut occurrences: Vec<(usize, usize, AppearanceKind)> = Vec::new();
+
+        let links = find_wikilinks(body);
+        for link in &links {
+            if id_map.resolve(&link.target).is_some_and(is_character) {
+                occurrences.push((link.start, link.end, AppearanceKind::Link));
+            }
+        }
+
+        // Names inside links are already covered by the link itself
+        for found in names_regex.find_iter(body) {
+            if links.iter().any(|link| found.start() < link.end && link.start < found.end()) {
+                continue;
+            }
+            let kind = if found.as_str().to_lowercase() == title {
+                AppearanceKind::Mention
+            } else {
+                AppearanceKind::Alias
+            };
+            occurrences.push((found.start(), found.end(), kind));
+        }
+
+        if occurrences.is_empty() {
+            continue;
+        }
+        occurrences.sort_by_key(|&(start, _, _)| start);
+
+        let mut kinds = Vec::new();
+        for &(_, _, kind) in &occurrences {
+            if !kinds.contains(&kind) {
+                kinds.push(kind);
+            }
+        }
+        let snippets = occurrences
+            .iter()
+            .take(MAX_SNIPPETS)
+            .map(|&(start, end, _)| resolve_wikilinks(&snippet(body, start, end), &id_map))
+            .collect();
+
+        appearances.push(CharacterAppearance {
+            path: document.path,
+            title: document.title,
+            kinds,
+            occurrences: occurrences.len(),
+            snippets,
+        });
+    }
+
+    Ok(CharacterReport {
+        character_id: character.id,
+        name: character.title,
+        aliases,
+        first_appearance: appearances.first().map(|a| a.path.clone()),
+        last_appearance: appearances.last().map(|a| a.path.clone()),
+        appearances,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::project_service::create_project;
+    use std::env;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_character_report_lists_scenes_in_order() {
+        let temp_dir = env::temp_dir().join("aycd_character_test_report");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let project = create_project("novel", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+        let anna_path = project_path.join("WORLD/Cast/Anna.md");
+        fs::write(&anna_path, "---\nid: anna\ntitle: Anna\naliases: [Annie]\n---\n\nThe heroine.").unwrap();
+
+        let drafts = project_path.join("NARRATIVE/Drafts");
+        fs::write(
+            drafts.join("B Storm.md"),
+            "---\ntitle: Storm\norder: 2\n---\n\nThe storm broke. [[anna|She]] ran, and Annie laughed.",
+        )
+        .unwrap();
+        fs::write(
+            drafts.join("A Harbor.md"),
+            "---\ntitle: Harbor\norder: 1\n---\n\nAt the harbor, anna waited for the boat.",
+        )
+        .unwrap();
+        fs::write(drafts.join("C Empty.md"), "---\ntitle: Empty\norder: 3\n---\n\nNobody here. Annabel left.").unwrap();
+        fs::write(drafts.join("D Broken.md"), [0xff, 0xfe, b'A', b'n', b'n', b'a']).unwrap();
+
+        // A different spelling of the same path still finds the links
+        let report = character_report(&project_path, &project_path.join("WORLD/Cast/../Cast/Anna.md")).unwrap();
+        assert_eq!(report.character_id, "anna");
+        assert_eq!(report.aliases, vec!["Annie"]);
+
+        let titles: Vec<&str> = report.appearances.iter().map(|a| a.title.as_str()).collect();
+        assert_eq!(titles, vec!["Harbor", "Storm"]);
+        assert_eq!(report.first_appearance.as_deref(), Some(report.appearances[0].path.as_str()));
+        assert_eq!(report.last_appearance.as_deref(), Some(report.appearances[1].path.as_str()));
+
+        let harbor = &report.appearances[0];
+        assert_eq!(harbor.kinds, vec![AppearanceKind::Mention]);
+        assert_eq!(harbor.snippets, vec!["At the harbor, anna waited for the boat."]);
+
+        let storm = &report.appearances[1];
+        assert_eq!(storm.kinds, vec![AppearanceKind::Link, AppearanceKind::Alias]);
+        assert_eq!(storm.occurrences, 2);
+        assert!(storm.snippets[0].contains("She ran"));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+}
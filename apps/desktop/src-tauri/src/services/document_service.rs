@@ -1,11 +1,22 @@
 use anyhow::{Context, Result};
 use chrono::Utc;
+use regex::{NoExpand, Regex, RegexBuilder};
 use serde_json;
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::models::{Document, DocumentType};
-use super::file_service::{ensure_dir, write_file, read_file};
+use crate::models::{ActivityKind, Document, DocumentType, KeyInfo, PromoteOutcome, ReplaceOptions};
+use super::activity_service::log_document_activity;
+use super::archive_service::ensure_not_archived;
+use super::file_service::{ensure_dir, write_file, read_file, touch_file};
+use super::link_service::build_id_map;
+use super::meta_cache_service::{list_documents, project_frontmatter};
+use super::settings_service::{config_path, load_app_config, resolve_author_in};
+use super::stats_service::{record_document_words, recount_document_words};
 
 /// Creates a new document in the specified category
 pub fn create_document(
@@ -15,20 +26,20 @@ pub fn create_document(
     category: &str,
     subcategory: Option<&str>,
 ) -> Result<Document> {
-    // Determine root directory based on document type
-    let root_dir = match document_type {
-        DocumentType::World => "WORLD",
-        DocumentType::Narrative => "NARRATIVE",
-    };
+    create_document_with(project_path, title, document_type, category, subcategory, &config_path()?)
+}
 
+/// Creates a new document, taking the fallback author from the given config
+fn create_document_with(
+    project_path: &Path,
+    title: &str,
+    document_type: DocumentType,
+    category: &str,
+    subcategory: Option<&str>,
+    config_path: &Path,
+) -> Result<Document> {
     // Build the document path
-    let mut doc_path = project_path.to_path_buf();
-    doc_path.push(root_dir);
-    doc_path.push(category);
-
-    if let Some(subcat) = subcategory {
-        doc_path.push(subcat);
-    }
+    let mut doc_path = category_dir(project_path, &document_type, category, subcategory);
 
     // Ensure directory exists
     ensure_dir(&doc_path)?;
@@ -44,10 +55,7 @@ pub fn create_document(
 
     // Create document metadata
     let now = Utc::now().timestamp();
-    let doc_type_str = match document_type {
-        DocumentType::World => "world",
-        DocumentType::Narrative => "narrative",
-    };
+    let doc_type_str = document_type_name(&document_type);
 
     let document = Document {
         id: uuid::Uuid::new_v4().to_string(),
@@ -60,14 +68,22 @@ pub fn create_document(
         created_at: now,
         modified_at: now,
         metadata: None,
+        metrics_stale: false,
     };
 
+    // Only write an author line when one is configured
+    let author_line = resolve_author_in(config_path, project_path)
+        .map(|author| format!("author: {}\n", author))
+        .unwrap_or_default();
+
     // Write empty markdown file with frontmatter
     let content = format!(
-        "---\nid: {}\ntitle: {}\ntype: {}\ncreated: {}\n---\n\n# {}\n\n",
-        document.id, title, doc_type_str, now, title
+        "---\nid: {}\ntitle: {}\ntype: {}\ncreated: {}\n{}---\n\n# {}\n\n",
+        document.id, title, doc_type_str, now, author_line, title
     );
     write_file(&doc_path, &content)?;
+    log_document_activity(ActivityKind::Create, &doc_path, None);
+    record_document_words(&doc_path, None, Some(count_words(strip_frontmatter(&content))));
 
     Ok(document)
 }
@@ -79,8 +95,24 @@ pub fn read_document(document_path: &Path) -> Result<Document> {
     }
 
     let content = read_file(document_path)?;
+    document_from_content(document_path, content, None)
+}
+
+/// Builds a document from its file content. A known `word_count` skips
+/// recomputing prose metrics.
+fn document_from_content(document_path: &Path, content: String, word_count: Option<usize>) -> Result<Document> {
     let metadata = fs::metadata(document_path)?;
+    Ok(parse_document(document_path, content, &metadata, word_count))
+}
 
+/// Builds a document from raw content, using `metadata` for timestamps the
+/// frontmatter doesn't provide
+pub fn parse_document(
+    document_path: &Path,
+    content: String,
+    metadata: &fs::Metadata,
+    word_count: Option<usize>,
+) -> Document {
     // Parse frontmatter if present
     let (frontmatter, body) = parse_frontmatter(&content);
 
@@ -88,17 +120,20 @@ pub fn read_document(document_path: &Path) -> Result<Document> {
     let id = frontmatter
         .get("id")
         .and_then(|v| v.as_str())
-        .unwrap_or_else(|| "")
+        .unwrap_or("")
         .to_string();
 
     let title = frontmatter
         .get("title")
         .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
         .or_else(|| {
             // Extract title from first heading
             body.lines()
                 .find(|line| line.starts_with("# "))
                 .map(|line| line.trim_start_matches("# ").trim())
+                .filter(|t| !t.is_empty())
         })
         .unwrap_or_else(|| {
             // Use filename as fallback
@@ -128,7 +163,7 @@ pub fn read_document(document_path: &Path) -> Result<Document> {
         .map(|d| d.as_secs() as i64)
         .unwrap_or(0);
 
-    let word_count = count_words(&body);
+    let word_count = word_count.unwrap_or_else(|| count_words(&body));
 
     // Determine document type from frontmatter or path
     let document_type = frontmatter
@@ -152,7 +187,13 @@ pub fn read_document(document_path: &Path) -> Result<Document> {
         })
         .unwrap_or(DocumentType::World); // Default to World if undetermined
 
-    Ok(Document {
+    // Expose the raw frontmatter so the UI can read status, tags, etc.
+    let metadata = match frontmatter {
+        serde_json::Value::Object(ref map) if !map.is_empty() => Some(frontmatter.clone()),
+        _ => None,
+    };
+
+    Document {
         id,
         project_id: String::new(),
         path: document_path.to_string_lossy().to_string(),
@@ -162,54 +203,426 @@ pub fn read_document(document_path: &Path) -> Result<Document> {
         word_count,
         created_at,
         modified_at,
-        metadata: None,
-    })
+        metadata,
+        metrics_stale: false,
+    }
 }
 
 /// Updates a document's content
 pub fn update_document(document_path: &Path, content: &str) -> Result<()> {
+    write_document_content(document_path, content)?;
+    log_document_activity(ActivityKind::Update, document_path, None);
+    record_document_words(document_path, None, Some(count_words(strip_frontmatter(content))));
+    Ok(())
+}
+
+/// Writes an existing document's content without logging activity or
+/// touching the project stats
+fn write_document_content(document_path: &Path, content: &str) -> Result<()> {
+    ensure_not_archived(document_path)?;
+
     if !document_path.exists() {
         anyhow::bail!("Document not found: {}", document_path.display());
     }
 
-    write_file(document_path, content)?;
+    write_file(document_path, content)
+}
+
+/// Builds the regex used for find/replace from the search text and options
+pub fn build_search_regex(find: &str, options: &ReplaceOptions) -> Result<Regex> {
+    if find.is_empty() {
+        anyhow::bail!("Search text cannot be empty");
+    }
+
+    let pattern = if options.regex {
+        find.to_string()
+    } else {
+        regex::escape(find)
+    };
+    let pattern = if options.whole_word {
+        format!(r"\b(?:{})\b", pattern)
+    } else {
+        pattern
+    };
+
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!options.case_sensitive)
+        .build()
+        .with_context(|| format!("Invalid search pattern: {}", find))
+}
+
+/// Default minimum time between word-count recomputations on quick saves
+pub const DEFAULT_METRICS_INTERVAL_MS: u64 = 1000;
+
+/// Shared throttle used by quick saves
+static METRICS_THROTTLE: OnceLock<Mutex<MetricsThrottle>> = OnceLock::new();
+
+/// Remembers when each document's metrics were last computed
+#[derive(Debug)]
+pub(crate) struct MetricsThrottle {
+    interval: Duration,
+    computed: HashMap<PathBuf, ComputedMetrics>,
+    /// Whether a background thread is already waiting to flush due windows
+    flush_scheduled: bool,
+}
+
+/// The word count last computed for a document
+#[derive(Debug, Clone, Copy)]
+struct ComputedMetrics {
+    at: Instant,
+    word_count: usize,
+    /// Set when a throttled save has happened since `at`
+    pending: bool,
+}
+
+impl MetricsThrottle {
+    pub(crate) fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            computed: HashMap::new(),
+            flush_scheduled: false,
+        }
+    }
+
+    /// Returns the last word count if it was computed within the interval
+    fn cached_word_count(&self, path: &Path, now: Instant) -> Option<usize> {
+        self.computed
+            .get(path)
+            .filter(|metrics| now.saturating_duration_since(metrics.at) < self.interval)
+            .map(|metrics| metrics.word_count)
+    }
+
+    /// Records a freshly computed word count
+    fn record(&mut self, path: &Path, word_count: usize, now: Instant) {
+        let metrics = ComputedMetrics { at: now, word_count, pending: false };
+        self.computed.insert(path.to_path_buf(), metrics);
+    }
+
+    /// Notes that a document was saved without recomputing its word count
+    fn mark_pending(&mut self, path: &Path) {
+        if let Some(metrics) = self.computed.get_mut(path) {
+            metrics.pending = true;
+        }
+    }
+
+    /// When the earliest open window closes, if any document is tracked
+    fn next_due(&self) -> Option<Instant> {
+        self.computed.values().map(|metrics| metrics.at + self.interval).min()
+    }
+
+    /// Closes every window that has passed. Documents saved during their
+    /// window are recounted from disk so the last save isn't left stale;
+    /// the rest are forgotten, ending their burst of saves.
+    pub(crate) fn flush_due(&mut self, now: Instant) {
+        let due: Vec<(PathBuf, bool)> = self
+            .computed
+            .iter()
+            .filter(|(_, metrics)| now.saturating_duration_since(metrics.at) >= self.interval)
+            .map(|(path, metrics)| (path.clone(), metrics.pending))
+            .collect();
+
+        for (path, pending) in due {
+            match pending.then(|| recount_document_words(&path)).flatten() {
+                Some(word_count) => self.record(&path, word_count, now),
+                None => {
+                    self.computed.remove(&path);
+                }
+            }
+        }
+    }
+}
+
+/// Locks the shared throttle, reading the configured interval on first use
+fn metrics_throttle() -> Result<MutexGuard<'static, MetricsThrottle>> {
+    METRICS_THROTTLE
+        .get_or_init(|| {
+            let interval = load_app_config().ok().and_then(|config| config.metrics_interval_ms);
+            Mutex::new(MetricsThrottle::new(metrics_interval(interval)))
+        })
+        .lock()
+        .map_err(|_| anyhow::anyhow!("Metrics throttle lock poisoned"))
+}
+
+/// Resolves a configured `metricsIntervalMs`, falling back to the default
+fn metrics_interval(interval_ms: Option<u64>) -> Duration {
+    Duration::from_millis(interval_ms.unwrap_or(DEFAULT_METRICS_INTERVAL_MS))
+}
+
+/// Applies a changed `metricsIntervalMs` to later quick saves
+pub fn set_metrics_interval(interval_ms: Option<u64>) -> Result<()> {
+    metrics_throttle()?.interval = metrics_interval(interval_ms);
     Ok(())
 }
 
+/// Flushes the shared throttle as each window closes, until no document is
+/// tracked anymore
+fn flush_metrics_in_background() {
+    loop {
+        let due = {
+            let Ok(mut throttle) = metrics_throttle() else {
+                return;
+            };
+            match throttle.next_due() {
+                Some(due) => due,
+                None => {
+                    throttle.flush_scheduled = false;
+                    return;
+                }
+            }
+        };
+
+        thread::sleep(due.saturating_duration_since(Instant::now()));
+        if let Ok(mut throttle) = metrics_throttle() {
+            throttle.flush_due(Instant::now());
+        }
+    }
+}
+
+/// Saves a document's content immediately, recomputing its word count at
+/// most once per `metricsIntervalMs`. In between, the last count is reused
+/// and the returned document is flagged with `metrics_stale`; the count is
+/// refreshed in the background once the window closes.
+pub fn quick_save_document(document_path: &Path, content: &str) -> Result<Document> {
+    let mut throttle = metrics_throttle()?;
+    let document = quick_save_with(&mut throttle, document_path, content, Instant::now())?;
+
+    if !throttle.flush_scheduled {
+        throttle.flush_scheduled = true;
+        thread::spawn(flush_metrics_in_background);
+    }
+
+    Ok(document)
+}
+
+/// Quick save against an explicit throttle and clock
+pub(crate) fn quick_save_with(
+    throttle: &mut MetricsThrottle,
+    document_path: &Path,
+    content: &str,
+    now: Instant,
+) -> Result<Document> {
+    // Stats only follow recomputed counts, keeping throttled saves cheap
+    write_document_content(document_path, content)?;
+
+    if let Some(word_count) = throttle.cached_word_count(document_path, now) {
+        throttle.mark_pending(document_path);
+        let mut document = document_from_content(document_path, content.to_string(), Some(word_count))?;
+        document.metrics_stale = true;
+        return Ok(document);
+    }
+
+    // A burst of saves shows up once in the activity log
+    if !throttle.computed.contains_key(document_path) {
+        log_document_activity(ActivityKind::Update, document_path, None);
+    }
+
+    let document = document_from_content(document_path, content.to_string(), None)?;
+    throttle.record(document_path, document.word_count, now);
+    record_document_words(document_path, None, Some(document.word_count));
+    Ok(document)
+}
+
+/// Replaces matches in a document's body (frontmatter untouched): every match,
+/// or only the nth (1-based) when `occurrence` is given
+pub fn replace_in_document(
+    document_path: &Path,
+    find: &str,
+    replace: &str,
+    options: &ReplaceOptions,
+    occurrence: Option<usize>,
+) -> Result<Document> {
+    ensure_not_archived(document_path)?;
+
+    if !document_path.exists() {
+        anyhow::bail!("Document not found: {}", document_path.display());
+    }
+
+    let regex = build_search_regex(find, options)?;
+    let content = read_file(document_path)?;
+    let body = strip_frontmatter(&content);
+    let frontmatter = &content[..content.len() - body.len()];
+
+    let new_body = match occurrence {
+        Some(n) => {
+            let caps = n
+                .checked_sub(1)
+                .and_then(|index| regex.captures_iter(body).nth(index))
+                .ok_or_else(|| anyhow::anyhow!("Occurrence {} of \"{}\" not found", n, find))?;
+            let found = caps.get(0).expect("capture group 0 is always the whole match");
+
+            let mut replacement = String::new();
+            if options.regex {
+                caps.expand(replace, &mut replacement);
+            } else {
+                replacement.push_str(replace);
+            }
+            format!("{}{}{}", &body[..found.start()], replacement, &body[found.end()..])
+        }
+        None if options.regex => regex.replace_all(body, replace).into_owned(),
+        None => regex.replace_all(body, NoExpand(replace)).into_owned(),
+    };
+
+    if new_body == body {
+        return read_document(document_path);
+    }
+
+    write_file(document_path, &format!("{}{}", frontmatter, new_body))?;
+    log_document_activity(ActivityKind::Update, document_path, None);
+
+    let document = read_document(document_path)?;
+    record_document_words(document_path, None, Some(document.word_count));
+    Ok(document)
+}
+
+/// Bumps a document's modification time without changing its content
+pub fn touch_document(document_path: &Path) -> Result<Document> {
+    ensure_not_archived(document_path)?;
+
+    if !document_path.exists() {
+        anyhow::bail!("Document not found: {}", document_path.display());
+    }
+
+    touch_file(document_path)?;
+    read_document(document_path)
+}
+
 /// Deletes a document
 pub fn delete_document(document_path: &Path) -> Result<()> {
+    ensure_not_archived(document_path)?;
+
     if !document_path.exists() {
         anyhow::bail!("Document not found: {}", document_path.display());
     }
 
     fs::remove_file(document_path)
         .with_context(|| format!("Failed to delete document: {}", document_path.display()))?;
+    log_document_activity(ActivityKind::Delete, document_path, None);
+    record_document_words(document_path, None, None);
 
     Ok(())
 }
 
-/// Lists all documents in a directory
-pub fn list_documents_in_dir(dir_path: &Path) -> Result<Vec<Document>> {
-    if !dir_path.exists() {
-        return Ok(Vec::new());
+/// Moves a document into another category, updating its type when the
+/// category belongs to the other mode
+pub fn move_document(
+    project_path: &Path,
+    document_path: &Path,
+    category: &str,
+    subcategory: Option<&str>,
+) -> Result<Document> {
+    ensure_not_archived(document_path)?;
+    let document = read_document(document_path)?;
+    let document_type = document_type_for_category(category)
+        .unwrap_or_else(|| document.document_type.clone());
+
+    let target_dir = category_dir(project_path, &document_type, category, subcategory);
+    ensure_dir(&target_dir)?;
+
+    let file_name = document_path
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("Invalid document path: {}", document_path.display()))?;
+    let target_path = target_dir.join(file_name);
+
+    if target_path.exists() {
+        anyhow::bail!("Document already exists: {}", target_path.display());
     }
 
-    let mut documents = Vec::new();
+    fs::rename(document_path, &target_path)
+        .with_context(|| format!("Failed to move document to: {}", target_path.display()))?;
 
-    for entry in fs::read_dir(dir_path)? {
-        let entry = entry?;
-        let path = entry.path();
+    if document_type != document.document_type {
+        let content = read_file(&target_path)?;
+        let updated = set_frontmatter_value(&content, "type", document_type_name(&document_type));
+        write_file(&target_path, &updated)?;
+    }
+    log_document_activity(ActivityKind::Move, &target_path, Some(document_path));
 
-        if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("md") {
-            match read_document(&path) {
-                Ok(doc) => documents.push(doc),
-                Err(e) => eprintln!("Failed to read document {}: {}", path.display(), e),
-            }
+    let moved = read_document(&target_path)?;
+    record_document_words(&target_path, Some(document_path), Some(moved.word_count));
+    Ok(moved)
+}
+
+/// Moves a document identified by its id, wherever it currently lives
+pub fn move_document_by_id(
+    project_path: &Path,
+    document_id: &str,
+    category: &str,
+    subcategory: Option<&str>,
+) -> Result<Document> {
+    let id_map = build_id_map(project_path)?;
+    let entry = id_map
+        .get(document_id)
+        .ok_or_else(|| anyhow::anyhow!("Document not found: {}", document_id))?;
+
+    move_document(project_path, &entry.path, category, subcategory)
+}
+
+/// Sets the `status` frontmatter value of a document
+pub fn set_document_status(document_path: &Path, status: &str) -> Result<Document> {
+    ensure_not_archived(document_path)?;
+
+    if !document_path.exists() {
+        anyhow::bail!("Document not found: {}", document_path.display());
+    }
+
+    // A line break would smuggle extra keys into the frontmatter
+    let status = status.trim();
+    if status.contains(['\r', '\n']) {
+        anyhow::bail!("Status cannot contain line breaks");
+    }
+
+    let content = read_file(document_path)?;
+    write_file(document_path, &set_frontmatter_value(&content, "status", status))?;
+    log_document_activity(ActivityKind::Update, document_path, None);
+
+    read_document(document_path)
+}
+
+/// Promotes a NARRATIVE draft to the Final category and marks it final.
+/// Anything else is left untouched and reported with a message.
+pub fn promote_document(document_path: &Path, project_path: &Path) -> Result<PromoteOutcome> {
+    let document = read_document(document_path)?;
+    let narrative_dir = project_path.join("NARRATIVE");
+
+    // Keep any nested folders (e.g. Drafts/Act 1 -> Final/Act 1)
+    let subcategory = document_path
+        .parent()
+        .and_then(|parent| parent.strip_prefix(narrative_dir.join("Drafts")).ok())
+        .map(|rel| rel.to_string_lossy().to_string());
+
+    let skipped = |message: &str| PromoteOutcome {
+        document: document.clone(),
+        promoted: false,
+        message: Some(message.to_string()),
+    };
+    let subcategory = match (&document.document_type, subcategory) {
+        (DocumentType::Narrative, Some(subcat)) => subcat,
+        (DocumentType::Narrative, None) if document_path.starts_with(narrative_dir.join("Final")) => {
+            return Ok(skipped("Document is already in Final"));
         }
+        _ => return Ok(skipped("Only NARRATIVE drafts can be promoted")),
+    };
+    let subcategory = Some(subcategory.as_str()).filter(|s| !s.is_empty());
+
+    let moved = move_document(project_path, document_path, "Final", subcategory)?;
+    Ok(PromoteOutcome {
+        document: set_document_status(Path::new(&moved.path), "final")?,
+        promoted: true,
+        message: None,
+    })
+}
+
+/// Lists all documents in a directory
+pub fn list_documents_in_dir(dir_path: &Path) -> Result<Vec<Document>> {
+    if !dir_path.exists() {
+        return Ok(Vec::new());
     }
 
+    // Unchanged files reuse their cached word counts
+    let mut documents = list_documents(dir_path)?;
+
     // Sort by modified date (most recent first)
-    documents.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+    documents.sort_by_key(|d| std::cmp::Reverse(d.modified_at));
 
     Ok(documents)
 }
@@ -218,39 +631,95 @@ pub fn list_documents_in_dir(dir_path: &Path) -> Result<Vec<Document>> {
 pub fn list_all_documents(project_path: &Path) -> Result<Vec<Document>> {
     let mut all_documents = Vec::new();
 
+    for path in collect_document_paths(project_path)? {
+        match read_document(&path) {
+            Ok(doc) => all_documents.push(doc),
+            Err(e) => eprintln!("Failed to read document {}: {}", path.display(), e),
+        }
+    }
+
+    // Sort by modified date
+    all_documents.sort_by_key(|d| std::cmp::Reverse(d.modified_at));
+
+    Ok(all_documents)
+}
+
+/// Lists the most recently modified documents in a project
+pub fn recent_documents(project_path: &Path, limit: usize) -> Result<Vec<Document>> {
+    let mut documents = list_all_documents(project_path)?;
+    documents.truncate(limit);
+    Ok(documents)
+}
+
+/// Collects the paths of all markdown documents in a project
+pub fn collect_document_paths(project_path: &Path) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+
     // Search in WORLD and NARRATIVE directories
     for main_category in &["WORLD", "NARRATIVE"] {
         let category_path = project_path.join(main_category);
         if category_path.exists() {
-            collect_documents_recursive(&category_path, &mut all_documents)?;
+            collect_paths_recursive(&category_path, &mut paths)?;
         }
     }
 
-    // Sort by modified date
-    all_documents.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
-
-    Ok(all_documents)
+    Ok(paths)
 }
 
-/// Recursively collects documents from a directory
-fn collect_documents_recursive(dir: &Path, documents: &mut Vec<Document>) -> Result<()> {
+/// Recursively collects markdown file paths from a directory
+fn collect_paths_recursive(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<()> {
     for entry in fs::read_dir(dir)? {
         let entry = entry?;
         let path = entry.path();
 
         if path.is_dir() {
-            collect_documents_recursive(&path, documents)?;
+            collect_paths_recursive(&path, paths)?;
         } else if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("md") {
-            match read_document(&path) {
-                Ok(doc) => documents.push(doc),
-                Err(e) => eprintln!("Failed to read document {}: {}", path.display(), e),
-            }
+            paths.push(path);
         }
     }
 
     Ok(())
 }
 
+/// Maximum number of distinct sample values reported per frontmatter key
+const MAX_KEY_SAMPLES: usize = 5;
+
+/// Lists every frontmatter key used across a project, with occurrence counts
+/// and a few sample values, for building filter menus
+pub fn frontmatter_keys(project_path: &Path) -> Result<Vec<KeyInfo>> {
+    let mut keys: HashMap<String, KeyInfo> = HashMap::new();
+
+    // Unchanged documents are served from the metadata cache
+    for map in project_frontmatter(project_path)? {
+        for (key, value) in map {
+            let info = keys.entry(key.clone()).or_insert_with(|| KeyInfo {
+                key: key.clone(),
+                count: 0,
+                sample_values: Vec::new(),
+            });
+            info.count += 1;
+
+            let sample = match value {
+                serde_json::Value::String(s) => s,
+                other => other.to_string(),
+            };
+            if !sample.is_empty()
+                && info.sample_values.len() < MAX_KEY_SAMPLES
+                && !info.sample_values.contains(&sample)
+            {
+                info.sample_values.push(sample);
+            }
+        }
+    }
+
+    // Most common keys first, then alphabetical
+    let mut keys: Vec<KeyInfo> = keys.into_values().collect();
+    keys.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+
+    Ok(keys)
+}
+
 /// Sanitizes a filename by removing invalid characters
 fn sanitize_filename(name: &str) -> String {
     name.chars()
@@ -263,41 +732,231 @@ fn sanitize_filename(name: &str) -> String {
         .to_string()
 }
 
+/// Root folder for a document type
+fn document_type_root(document_type: &DocumentType) -> &'static str {
+    match document_type {
+        DocumentType::World => "WORLD",
+        DocumentType::Narrative => "NARRATIVE",
+    }
+}
+
+/// Frontmatter value for a document type
+fn document_type_name(document_type: &DocumentType) -> &'static str {
+    match document_type {
+        DocumentType::World => "world",
+        DocumentType::Narrative => "narrative",
+    }
+}
+
+/// Infers the document type from one of the standard project categories
+fn document_type_for_category(category: &str) -> Option<DocumentType> {
+    match category {
+        "Cast" | "Places" | "Objects" | "Systems" | "Lore" => Some(DocumentType::World),
+        "Drafts" | "Final" | "Research" | "Planning" => Some(DocumentType::Narrative),
+        _ => None,
+    }
+}
+
+/// Builds the directory for a category (and optional subcategory)
+fn category_dir(
+    project_path: &Path,
+    document_type: &DocumentType,
+    category: &str,
+    subcategory: Option<&str>,
+) -> PathBuf {
+    let mut dir = project_path.join(document_type_root(document_type));
+    dir.push(category);
+
+    if let Some(subcat) = subcategory {
+        dir.push(subcat);
+    }
+
+    dir
+}
+
+/// Splits markdown content into its raw frontmatter block and body.
+/// The closing `---` may end the file, and CRLF line endings are accepted.
+fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    let rest = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))?;
+    let start = content.len() - rest.len();
+
+    let mut offset = start;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end_matches(['\n', '\r']) == "---" {
+            let frontmatter = content[start..offset]
+                .strip_suffix('\n')
+                .map(|s| s.strip_suffix('\r').unwrap_or(s))
+                .unwrap_or(&content[start..offset]);
+            return Some((frontmatter, &content[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+
+    None
+}
+
+/// Returns the body of a document without its frontmatter
+pub fn strip_frontmatter(content: &str) -> &str {
+    split_frontmatter(content).map_or(content, |(_, body)| body)
+}
+
 /// Parses YAML frontmatter from markdown content
 fn parse_frontmatter(content: &str) -> (serde_json::Value, String) {
-    if !content.starts_with("---\n") {
+    let Some((frontmatter_str, body)) = split_frontmatter(content) else {
         return (serde_json::json!({}), content.to_string());
+    };
+
+    // Parse YAML frontmatter as JSON (simple key-value pairs)
+    let mut map = serde_json::Map::new();
+    for line in frontmatter_str.lines() {
+        if let Some((key, value)) = line.split_once(':') {
+            let key = key.trim().to_string();
+            let value = value.trim();
+
+            // Try to parse as number first, then string
+            if let Ok(num) = value.parse::<i64>() {
+                map.insert(key, serde_json::json!(num));
+            } else {
+                map.insert(key, serde_json::json!(value));
+            }
+        }
     }
 
-    // Find the end of frontmatter
-    if let Some(end_pos) = content[4..].find("\n---\n") {
-        let frontmatter_str = &content[4..end_pos + 4];
-        let body = &content[end_pos + 9..];
+    (serde_json::Value::Object(map), body.to_string())
+}
 
-        // Parse YAML frontmatter as JSON (simple key-value pairs)
-        let mut map = serde_json::Map::new();
-        for line in frontmatter_str.lines() {
-            if let Some((key, value)) = line.split_once(':') {
-                let key = key.trim().to_string();
-                let value = value.trim();
+/// Sets a frontmatter key, replacing an existing line or appending a new one.
+/// Content without frontmatter gets a fresh block.
+pub fn set_frontmatter_value(content: &str, key: &str, value: &str) -> String {
+    let Some((frontmatter_str, body)) = split_frontmatter(content) else {
+        return format!("---\n{}: {}\n---\n\n{}", key, value, content);
+    };
 
-                // Try to parse as number first, then string
-                if let Ok(num) = value.parse::<i64>() {
-                    map.insert(key, serde_json::json!(num));
-                } else {
-                    map.insert(key, serde_json::json!(value));
-                }
+    let mut lines = Vec::new();
+    let mut replaced = false;
+    for line in frontmatter_str.lines() {
+        match line.split_once(':') {
+            Some((k, _)) if k.trim() == key => {
+                lines.push(format!("{}: {}", key, value));
+                replaced = true;
+            }
+            _ => lines.push(line.to_string()),
+        }
+    }
+    if !replaced {
+        lines.push(format!("{}: {}", key, value));
+    }
+
+    format!("---\n{}\n---\n{}", lines.join("\n"), body)
+}
+
+/// Frontmatter keys that lead, in this order, when normalizing
+const CANONICAL_FRONTMATTER_KEYS: [&str; 5] = ["id", "title", "type", "created", "modified"];
+
+/// Reorders frontmatter lines canonically: id, title, type, created and
+/// modified first, then the remaining keys alphabetically. Lines are moved
+/// verbatim (lines without a key travel with the key above them) and
+/// everything after the frontmatter is left byte-for-byte intact.
+pub fn normalize_frontmatter_order(content: &str) -> String {
+    let Some((frontmatter_str, body)) = split_frontmatter(content) else {
+        return content.to_string();
+    };
+
+    // Opening fence and closing fence, kept with their original line endings
+    let header = &content[..content.len() - body.len()];
+    let frontmatter_start = header.find('\n').map_or(header.len(), |i| i + 1);
+    let frontmatter_end = frontmatter_start + frontmatter_str.len();
+    if frontmatter_str.is_empty() || frontmatter_end > header.len() {
+        return content.to_string();
+    }
+
+    // Group each key line with any continuation lines below it
+    let mut leading: Vec<&str> = Vec::new();
+    let mut entries: Vec<(String, Vec<&str>)> = Vec::new();
+    for line in frontmatter_str.lines() {
+        match line.split_once(':').map(|(key, _)| key.trim()) {
+            Some(key) if !key.is_empty() && !line.starts_with([' ', '\t', '#']) => {
+                entries.push((key.to_string(), vec![line]));
             }
+            _ => match entries.last_mut() {
+                Some((_, lines)) => lines.push(line),
+                None => leading.push(line),
+            },
         }
+    }
+
+    let rank = |key: &str| {
+        CANONICAL_FRONTMATTER_KEYS
+            .iter()
+            .position(|&canonical| canonical == key)
+            .unwrap_or(CANONICAL_FRONTMATTER_KEYS.len())
+    };
+    entries.sort_by(|(a, _), (b, _)| (rank(a), a).cmp(&(rank(b), b)));
+
+    let line_ending = if frontmatter_str.contains("\r\n") { "\r\n" } else { "\n" };
+    let lines: Vec<&str> = leading
+        .into_iter()
+        .chain(entries.iter().flat_map(|(_, lines)| lines.iter().copied()))
+        .collect();
 
-        return (serde_json::Value::Object(map), body.to_string());
+    format!(
+        "{}{}{}{}",
+        &header[..frontmatter_start],
+        lines.join(line_ending),
+        &header[frontmatter_end..],
+        body
+    )
+}
+
+/// Rewrites a document's frontmatter in canonical key order. The file is
+/// only written when the order actually changes.
+pub fn normalize_frontmatter(document_path: &Path) -> Result<Document> {
+    ensure_not_archived(document_path)?;
+
+    if !document_path.exists() {
+        anyhow::bail!("Document not found: {}", document_path.display());
+    }
+
+    let content = read_file(document_path)?;
+    let normalized = normalize_frontmatter_order(&content);
+    if normalized == content {
+        return document_from_content(document_path, content, None);
     }
 
-    (serde_json::json!({}), content.to_string())
+    write_file(document_path, &normalized)?;
+    log_document_activity(ActivityKind::Update, document_path, None);
+
+    read_document(document_path)
+}
+
+/// Normalizes the frontmatter of every document in a project, returning how
+/// many documents were rewritten. Unreadable documents are logged and skipped.
+pub fn normalize_project_frontmatter(project_path: &Path) -> Result<usize> {
+    let mut rewritten = 0;
+
+    for path in collect_document_paths(project_path)? {
+        let content = match read_file(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("Skipping frontmatter normalization for {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        let normalized = normalize_frontmatter_order(&content);
+        if normalized != content {
+            write_file(&path, &normalized)?;
+            log_document_activity(ActivityKind::Update, &path, None);
+            rewritten += 1;
+        }
+    }
+
+    Ok(rewritten)
 }
 
 /// Counts words in text
-fn count_words(text: &str) -> usize {
+pub fn count_words(text: &str) -> usize {
     text.split_whitespace().count()
 }
 
@@ -325,13 +984,348 @@ mod tests {
         let _ = fs::remove_dir_all(&temp_dir);
         fs::create_dir_all(&temp_dir).unwrap();
 
-        let doc = create_document(&temp_dir, "Test Chapter", "NARRATIVE", Some("Drafts")).unwrap();
+        let doc = create_document(&temp_dir, "Test Chapter", DocumentType::Narrative, "Drafts", None).unwrap();
         assert_eq!(doc.title, "Test Chapter");
-        assert!(doc.path.contains("Test-Chapter.md"));
+        assert!(doc.path.contains("Test Chapter.md"));
 
         let read_doc = read_document(&PathBuf::from(&doc.path)).unwrap();
         assert_eq!(read_doc.title, "Test Chapter");
 
         fs::remove_dir_all(&temp_dir).unwrap();
     }
+
+    #[test]
+    fn test_frontmatter_keys() {
+        let temp_dir = env::temp_dir().join("aycd_doc_test_keys");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let drafts = temp_dir.join("NARRATIVE/Drafts");
+        fs::create_dir_all(&drafts).unwrap();
+        fs::write(drafts.join("One.md"), "---\nstatus: draft\npov: Anna\n---\n\nText").unwrap();
+        fs::write(drafts.join("Two.md"), "---\nstatus: final\npov: Anna\n---\n\nText").unwrap();
+        fs::write(drafts.join("Three.md"), "---\nstatus: draft\n---\n\nText").unwrap();
+
+        let keys = frontmatter_keys(&temp_dir).unwrap();
+
+        let status = keys.iter().find(|k| k.key == "status").unwrap();
+        assert_eq!(status.count, 3);
+        assert_eq!(status.sample_values.len(), 2);
+
+        let pov = keys.iter().find(|k| k.key == "pov").unwrap();
+        assert_eq!(pov.count, 2);
+        assert_eq!(pov.sample_values, vec!["Anna".to_string()]);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_promote_document() {
+        let temp_dir = env::temp_dir().join("aycd_doc_test_promote");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let doc = create_document(&temp_dir, "Chapter One", DocumentType::Narrative, "Drafts", None).unwrap();
+        let outcome = promote_document(Path::new(&doc.path), &temp_dir).unwrap();
+        assert!(outcome.promoted);
+        assert!(outcome.message.is_none());
+        let promoted = outcome.document;
+
+        let final_path = temp_dir.join("NARRATIVE/Final/Chapter One.md");
+        assert_eq!(PathBuf::from(&promoted.path), final_path);
+        assert!(!Path::new(&doc.path).exists());
+        assert_eq!(promoted.id, doc.id);
+        assert_eq!(promoted.metadata.unwrap()["status"], "final");
+
+        // Promoting again is a no-op with a message
+        let again = promote_document(&final_path, &temp_dir).unwrap();
+        assert!(!again.promoted);
+        assert_eq!(again.message.as_deref(), Some("Document is already in Final"));
+        assert_eq!(PathBuf::from(&again.document.path), final_path);
+
+        // So is promoting a WORLD document, which stays where it is
+        let anna = create_document(&temp_dir, "Anna", DocumentType::World, "Cast", None).unwrap();
+        let world = promote_document(Path::new(&anna.path), &temp_dir).unwrap();
+        assert!(!world.promoted);
+        assert_eq!(world.message.as_deref(), Some("Only NARRATIVE drafts can be promoted"));
+        assert!(Path::new(&anna.path).exists());
+        assert!(read_document(Path::new(&anna.path)).unwrap().metadata.unwrap().get("status").is_none());
+
+        // Status values can't inject extra frontmatter keys
+        assert!(set_document_status(&final_path, "draft\nid: x").is_err());
+        assert_eq!(read_document(&final_path).unwrap().id, doc.id);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_move_document_by_id_after_relocation() {
+        let temp_dir = env::temp_dir().join("aycd_doc_test_move_by_id");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let doc = create_document(&temp_dir, "Old Map", DocumentType::World, "Places", None).unwrap();
+
+        // Relocated behind the caller's back; the stale path no longer exists
+        move_document(&temp_dir, Path::new(&doc.path), "Lore", Some("Maps")).unwrap();
+        assert!(!Path::new(&doc.path).exists());
+
+        let moved = move_document_by_id(&temp_dir, &doc.id, "Research", None).unwrap();
+        assert_eq!(PathBuf::from(&moved.path), temp_dir.join("NARRATIVE/Research/Old Map.md"));
+        assert_eq!(moved.document_type, DocumentType::Narrative);
+        assert_eq!(moved.id, doc.id);
+
+        assert!(move_document_by_id(&temp_dir, "missing-id", "Cast", None).is_err());
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_touch_document_moves_to_top_of_recents() {
+        let temp_dir = env::temp_dir().join("aycd_doc_test_touch");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let older = create_document(&temp_dir, "Older", DocumentType::World, "Cast", None).unwrap();
+        let newer = create_document(&temp_dir, "Newer", DocumentType::World, "Cast", None).unwrap();
+
+        // Backdate both files so the touch is clearly the latest change
+        let now = std::time::SystemTime::now();
+        for (doc, age) in [(&older, 7200), (&newer, 3600)] {
+            let file = fs::OpenOptions::new().write(true).open(&doc.path).unwrap();
+            file.set_modified(now - std::time::Duration::from_secs(age)).unwrap();
+        }
+        assert_eq!(recent_documents(&temp_dir, 10).unwrap()[0].id, newer.id);
+
+        let content_before = fs::read_to_string(&older.path).unwrap();
+        let before = read_document(Path::new(&older.path)).unwrap();
+        let touched = touch_document(Path::new(&older.path)).unwrap();
+        assert!(touched.modified_at > before.modified_at);
+        assert_eq!(fs::read_to_string(&older.path).unwrap(), content_before);
+
+        let recent = recent_documents(&temp_dir, 1).unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].id, older.id);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_create_document_writes_configured_author() {
+        let temp_dir = env::temp_dir().join("aycd_doc_test_author");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let mut project = crate::services::project_service::create_project("novel", Some(temp_dir.clone())).unwrap();
+        project.settings.author = Some("Jane Writer".to_string());
+        crate::services::project_service::update_project(&project).unwrap();
+        let project_path = PathBuf::from(&project.path);
+
+        let config = temp_dir.join("config.json");
+        fs::write(&config, r#"{"author": "App Default"}"#).unwrap();
+
+        let doc = create_document_with(&project_path, "Opening", DocumentType::Narrative, "Drafts", None, &config)
+            .unwrap();
+        let content = fs::read_to_string(&doc.path).unwrap();
+        assert!(content.contains("\nauthor: Jane Writer\n"));
+
+        let read_doc = read_document(Path::new(&doc.path)).unwrap();
+        assert_eq!(read_doc.metadata.unwrap()["author"], "Jane Writer");
+
+        // Without a project author, the app-wide default is used
+        project.settings.author = None;
+        crate::services::project_service::update_project(&project).unwrap();
+        let doc = create_document_with(&project_path, "Second", DocumentType::Narrative, "Drafts", None, &config)
+            .unwrap();
+        assert!(fs::read_to_string(&doc.path).unwrap().contains("\nauthor: App Default\n"));
+
+        // With neither, no author line is written
+        fs::write(&config, "{}").unwrap();
+        let doc = create_document_with(&project_path, "Third", DocumentType::Narrative, "Drafts", None, &config)
+            .unwrap();
+        assert!(!fs::read_to_string(&doc.path).unwrap().contains("author:"));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_empty_and_frontmatter_only_documents() {
+        let temp_dir = env::temp_dir().join("aycd_doc_test_empty");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let empty = temp_dir.join("Empty Note.md");
+        fs::write(&empty, "").unwrap();
+        let doc = read_document(&empty).unwrap();
+        assert_eq!(doc.title, "Empty Note");
+        assert_eq!(doc.word_count, 0);
+        assert!(doc.metadata.is_none());
+
+        // No body and no trailing newline after the closing delimiter
+        let frontmatter_only = temp_dir.join("Only Meta.md");
+        fs::write(&frontmatter_only, "---\nid: abc\ntitle:\n---").unwrap();
+        let doc = read_document(&frontmatter_only).unwrap();
+        assert_eq!(doc.id, "abc");
+        assert_eq!(doc.title, "Only Meta");
+        assert_eq!(doc.word_count, 0);
+
+        let crlf = temp_dir.join("Windows.md");
+        fs::write(&crlf, "---\r\nid: win\r\n---\r\n").unwrap();
+        let doc = read_document(&crlf).unwrap();
+        assert_eq!(doc.id, "win");
+        assert_eq!(doc.word_count, 0);
+
+        let empty_block = temp_dir.join("Empty Block.md");
+        fs::write(&empty_block, "---\n---\n").unwrap();
+        let doc = read_document(&empty_block).unwrap();
+        assert_eq!(doc.title, "Empty Block");
+        assert_eq!(doc.word_count, 0);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_normalize_frontmatter() {
+        let temp_dir = env::temp_dir().join("aycd_doc_test_normalize");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let body = "\n# Scene\n\nid: not frontmatter\r\n  trailing spaces  \n";
+        let scrambled = format!(
+            "---\nstatus: draft\nmodified: 1700000100\ntags: a, b\n  - continued\ntitle: Scene: One\ncreated: 1700000000\nauthor: Jane\nid: abc\ntype: narrative\n---\n{}",
+            body
+        );
+        let path = temp_dir.join("Scene.md");
+        fs::write(&path, &scrambled).unwrap();
+
+        let document = normalize_frontmatter(&path).unwrap();
+        let expected = format!(
+            "---\nid: abc\ntitle: Scene: One\ntype: narrative\ncreated: 1700000000\nmodified: 1700000100\nauthor: Jane\nstatus: draft\ntags: a, b\n  - continued\n---\n{}",
+            body
+        );
+        let written = fs::read_to_string(&path).unwrap();
+        assert_eq!(written, expected);
+        assert!(written.ends_with(body));
+        assert_eq!(document.id, "abc");
+        assert_eq!(document.title, "Scene: One");
+
+        // Already canonical content and files without frontmatter are unchanged
+        assert_eq!(normalize_frontmatter_order(&expected), expected);
+        assert_eq!(normalize_frontmatter_order("no frontmatter"), "no frontmatter");
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_normalize_project_frontmatter_skips_unreadable_documents() {
+        let temp_dir = env::temp_dir().join("aycd_doc_test_normalize_project");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let project = crate::services::project_service::create_project("novel", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+        let scrambled = project_path.join("NARRATIVE/Drafts/Scene.md");
+        fs::write(&scrambled, "---
+status: draft
+id: abc
+---
+
+Body").unwrap();
+        let canonical = project_path.join("NARRATIVE/Drafts/Other.md");
+        fs::write(&canonical, "---
+id: def
+status: draft
+---
+
+Body").unwrap();
+        // Not valid UTF-8, so it can't be read as text
+        fs::write(project_path.join("WORLD/Cast/Broken.md"), [0xff, 0xfe, 0x00]).unwrap();
+
+        assert_eq!(normalize_project_frontmatter(&project_path).unwrap(), 1);
+        assert_eq!(fs::read_to_string(&scrambled).unwrap(), "---\nid: abc\nstatus: draft\n---\n\nBody");
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_replace_in_document() {
+        let temp_dir = env::temp_dir().join("aycd_doc_test_replace");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let path = temp_dir.join("Scene.md");
+        fs::write(&path, "---\ntitle: Cat\n---\n\nThe cat sat. The Cat ran. The cat slept.").unwrap();
+
+        // Only the 2nd (case-insensitive) occurrence changes
+        let options = ReplaceOptions::default();
+        let doc = replace_in_document(&path, "cat", "dog", &options, Some(2)).unwrap();
+        assert!(doc.content.ends_with("The cat sat. The dog ran. The cat slept."));
+        assert!(doc.content.starts_with("---\ntitle: Cat\n---\n"));
+        assert!(replace_in_document(&path, "cat", "dog", &options, Some(5)).is_err());
+
+        // Regex with capture groups, case-sensitive, across the whole body
+        let options = ReplaceOptions {
+            regex: true,
+            case_sensitive: true,
+            whole_word: true,
+        };
+        let doc = replace_in_document(&path, r"The (\w+)", "A $1", &options, None).unwrap();
+        assert!(doc.content.ends_with("A cat sat. A dog ran. A cat slept."));
+        assert_eq!(doc.word_count, 9);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_quick_save_recomputes_metrics_once_per_window() {
+        let temp_dir = env::temp_dir().join("aycd_doc_test_quick_save");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let project = crate::services::project_service::create_project("novel", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+        let path = project_path.join("NARRATIVE/Drafts/Draft.md");
+        fs::write(&path, "").unwrap();
+
+        let interval = Duration::from_millis(500);
+        let mut throttle = MetricsThrottle::new(interval);
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+
+        let first = quick_save_with(&mut throttle, &path, "one", start).unwrap();
+        assert_eq!(first.word_count, 1);
+        assert!(!first.metrics_stale);
+
+        // Rapid saves persist content but reuse the count from the first save
+        let second = quick_save_with(&mut throttle, &path, "one two", at(100)).unwrap();
+        let third = quick_save_with(&mut throttle, &path, "one two three", at(200)).unwrap();
+        assert_eq!((second.word_count, third.word_count), (1, 1));
+        assert!(second.metrics_stale && third.metrics_stale);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "one two three");
+
+        // Nothing is due before the window closes
+        assert_eq!(throttle.next_due(), Some(at(500)));
+        throttle.flush_due(at(400));
+        assert_eq!(throttle.cached_word_count(&path, at(400)), Some(1));
+
+        // Closing the window recounts the last throttled save from disk
+        throttle.flush_due(at(500));
+        assert_eq!(throttle.cached_word_count(&path, at(600)), Some(3));
+
+        // Once the window has passed the count is refreshed
+        let later = quick_save_with(&mut throttle, &path, "one two three four", at(1000)).unwrap();
+        assert_eq!(later.word_count, 4);
+        assert!(!later.metrics_stale);
+
+        // With no further saves, the document is forgotten
+        throttle.flush_due(at(1500));
+        assert!(throttle.computed.is_empty());
+        assert_eq!(throttle.next_due(), None);
+
+        // The whole burst was logged as a single update
+        let updates = crate::services::activity_service::project_activity(&project_path, 100)
+            .unwrap()
+            .into_iter()
+            .filter(|activity| activity.kind == ActivityKind::Update)
+            .count();
+        assert_eq!(updates, 1);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
 }
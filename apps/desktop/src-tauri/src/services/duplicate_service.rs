@@ -0,0 +1,229 @@
+use anyhow::Result;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+use crate::models::DuplicateSet;
+use super::document_service::{collect_document_paths, strip_frontmatter};
+use super::file_service::read_file;
+
+/// Number of consecutive words per shingle for near-duplicate detection
+const SHINGLE_SIZE: usize = 3;
+
+/// Hashes a document body for content-addressable grouping
+fn hash_body(body: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    body.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Builds the set of lowercase word shingles for a body
+fn shingles(body: &str) -> HashSet<String> {
+    let words: Vec<String> = body.split_whitespace().map(|w| w.to_lowercase()).collect();
+    if words.len() < SHINGLE_SIZE {
+        return words.into_iter().collect();
+    }
+    words.windows(SHINGLE_SIZE).map(|w| w.join(" ")).collect()
+}
+
+/// Jaccard similarity of two shingle sets
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Groups documents whose bodies (frontmatter excluded) are identical. With a
+/// `similarity_threshold` in (0, 1], documents whose word shingles overlap at
+/// least that much are grouped as near-duplicates too.
+pub fn find_duplicate_documents(
+    project_path: &Path,
+    similarity_threshold: Option<f64>,
+) -> Result<Vec<DuplicateSet>> {
+    if let Some(threshold) = similarity_threshold {
+        if !(threshold > 0.0 && threshold <= 1.0) {
+            anyhow::bail!("Similarity threshold must be greater than 0 and at most 1, got {}", threshold);
+        }
+    }
+
+    let mut bodies: Vec<(String, String)> = Vec::new();
+    for path in collect_document_paths(project_path)? {
+        match read_file(&path) {
+            Ok(content) => {
+                let body = strip_frontmatter(&content).trim().to_string();
+                // Empty documents aren't meaningful duplicates
+                if !body.is_empty() {
+                    bodies.push((path.to_string_lossy().to_string(), body));
+                }
+            }
+            Err(e) => eprintln!("Failed to read document {}: {}", path.display(), e),
+        }
+    }
+    bodies.sort();
+
+    // Exact duplicates: same hash, confirmed by comparing the bodies
+    let mut by_hash: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (index, (_, body)) in bodies.iter().enumerate() {
+        by_hash.entry(hash_body(body)).or_default().push(index);
+    }
+
+    let mut sets = Vec::new();
+    let mut grouped = vec![false; bodies.len()];
+    let mut hidden = HashSet::new();
+    for indices in by_hash.values() {
+        for &first in indices {
+            if grouped[first] {
+                continue;
+            }
+            let members: Vec<usize> = indices
+                .iter()
+                .copied()
+                .filter(|&other| !grouped[other] && bodies[other].1 == bodies[first].1)
+                .collect();
+            if members.len() > 1 {
+                for &member in &members {
+                    grouped[member] = true;
+                }
+                // The first copy still takes part in near-duplicate matching
+                hidden.extend(members.iter().skip(1).copied());
+                sets.push(DuplicateSet {
+                    paths: members.iter().map(|&i| bodies[i].0.clone()).collect(),
+                    similarity: 1.0,
+                    exact: true,
+                });
+            }
+        }
+    }
+
+    if let Some(threshold) = similarity_threshold {
+        sets.extend(near_duplicates(&bodies, &hidden, threshold));
+    }
+
+    sets.sort_by(|a, b| a.paths.cmp(&b.paths));
+    Ok(sets)
+}
+
+/// Clusters documents whose similarity meets the threshold, skipping the
+/// extra copies of exact duplicates
+fn near_duplicates(bodies: &[(String, String)], hidden: &HashSet<usize>, threshold: f64) -> Vec<DuplicateSet> {
+    let candidates: Vec<usize> = (0..bodies.len()).filter(|i| !hidden.contains(i)).collect();
+    let shingle_sets: Vec<HashSet<String>> = candidates.iter().map(|&i| shingles(&bodies[i].1)).collect();
+
+    // Union-find over candidate positions
+    let mut parent: Vec<usize> = (0..candidates.len()).collect();
+    fn find(parent: &mut [usize], i: usize) -> usize {
+        let mut root = i;
+        while parent[root] != root {
+            root = parent[root];
+        }
+        parent[i] = root;
+        root
+    }
+
+    for a in 0..candidates.len() {
+        for b in (a + 1)..candidates.len() {
+            if jaccard(&shingle_sets[a], &shingle_sets[b]) < threshold {
+                continue;
+            }
+            let (root_a, root_b) = (find(&mut parent, a), find(&mut parent, b));
+            parent[root_b] = root_a;
+        }
+    }
+
+    let mut clusters: HashMap<usize, Vec<usize>> = HashMap::new();
+    for position in 0..candidates.len() {
+        let root = find(&mut parent, position);
+        clusters.entry(root).or_default().push(position);
+    }
+
+    clusters
+        .into_iter()
+        .filter(|(_, members)| members.len() > 1)
+        .map(|(_, members)| {
+            // Clusters chain through similar pairs, so members that were only
+            // linked indirectly can be less similar than the threshold
+            let lowest = members
+                .iter()
+                .enumerate()
+                .flat_map(|(i, &a)| members[i + 1..].iter().map(move |&b| (a, b)))
+                .map(|(a, b)| jaccard(&shingle_sets[a], &shingle_sets[b]))
+                .fold(1.0_f64, f64::min);
+
+            DuplicateSet {
+                paths: members.iter().map(|&p| bodies[candidates[p]].0.clone()).collect(),
+                similarity: lowest,
+                exact: false,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn test_find_duplicate_documents() {
+        let temp_dir = env::temp_dir().join("aycd_duplicate_test");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let cast = temp_dir.join("WORLD/Cast");
+        fs::create_dir_all(&cast).unwrap();
+
+        let body = "Anna grew up by the sea and never learned to swim.";
+        fs::write(cast.join("Anna.md"), format!("---\nid: a\ntitle: Anna\n---\n\n{}", body)).unwrap();
+        fs::write(cast.join("Anna copy.md"), format!("---\nid: b\ntitle: Anna (copy)\n---\n\n{}\n", body)).unwrap();
+        fs::write(
+            cast.join("Ben.md"),
+            "---\nid: c\n---\n\nAnna grew up by the sea and never learned to swim well.",
+        )
+        .unwrap();
+        fs::write(cast.join("Other.md"), "Something else entirely.").unwrap();
+
+        let exact = find_duplicate_documents(&temp_dir, None).unwrap();
+        assert_eq!(exact.len(), 1);
+        assert!(exact[0].exact);
+        assert_eq!(exact[0].paths.len(), 2);
+        assert!(exact[0].paths.iter().any(|p| p.ends_with("Anna.md")));
+        assert!(exact[0].paths.iter().any(|p| p.ends_with("Anna copy.md")));
+
+        // Ben differs by one word, so it only shows up as a near-duplicate
+        let near = find_duplicate_documents(&temp_dir, Some(0.5)).unwrap();
+        assert_eq!(near.len(), 2);
+        let similar = near.iter().find(|s| !s.exact).unwrap();
+        assert_eq!(similar.paths.len(), 2);
+        assert!(similar.paths.iter().any(|p| p.ends_with("Ben.md")));
+        assert!(similar.similarity >= 0.5 && similar.similarity < 1.0);
+        assert!(near.iter().all(|s| !s.paths.iter().any(|p| p.ends_with("Other.md"))));
+
+        // Thresholds outside (0, 1] are rejected
+        assert!(find_duplicate_documents(&temp_dir, Some(0.0)).is_err());
+        assert!(find_duplicate_documents(&temp_dir, Some(1.5)).is_err());
+        assert!(find_duplicate_documents(&temp_dir, Some(f64::NAN)).is_err());
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_near_duplicate_similarity_is_true_minimum() {
+        // a~b and b~c are similar, but a and c share much less
+        let bodies = vec![
+            ("a".to_string(), "one two three four five six seven eight".to_string()),
+            ("b".to_string(), "one two three four five six seven eight nine ten eleven".to_string()),
+            ("c".to_string(), "four five six seven eight nine ten eleven".to_string()),
+        ];
+        let threshold = 0.5;
+        let sets = near_duplicates(&bodies, &HashSet::new(), threshold);
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets[0].paths.len(), 3);
+
+        let shingle = |i: usize| shingles(&bodies[i].1);
+        let a_c = jaccard(&shingle(0), &shingle(2));
+        assert!(a_c < threshold);
+        assert_eq!(sets[0].similarity, a_c);
+    }
+}
@@ -0,0 +1,518 @@
+use anyhow::{Context, Result};
+use pulldown_cmark::{html, Options, Parser};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::models::{Document, DocumentSort, ExportFormat};
+use super::document_service::{collect_document_paths, read_document, strip_frontmatter};
+use super::file_service::ensure_dir;
+use super::link_service::{build_id_map, resolve_image_paths, resolve_wikilinks, transclude, IdMap};
+use super::meta_cache_service::list_document_meta;
+use super::project_service::{find_project_root, open_project};
+use super::settings_service::{config_path, resolve_author_in};
+
+/// NARRATIVE categories compiled into the manuscript
+const MANUSCRIPT_CATEGORIES: [&str; 2] = ["Drafts", "Final"];
+
+/// Separator placed between documents in a Markdown manuscript
+const MARKDOWN_SEPARATOR: &str = "\n\n* * *\n\n";
+
+/// Returns the manuscript documents in reading order: by the `order`
+/// frontmatter value first, then by path
+pub fn manuscript_paths(project_path: &Path) -> Result<Vec<PathBuf>> {
+    let manuscript_dirs: Vec<PathBuf> = MANUSCRIPT_CATEGORIES
+        .iter()
+        .map(|category| project_path.join("NARRATIVE").join(category))
+        .collect();
+
+    ordered_paths(project_path, &manuscript_dirs)
+}
+
+/// Returns every NARRATIVE document in reading order
+pub fn narrative_paths(project_path: &Path) -> Result<Vec<PathBuf>> {
+    ordered_paths(project_path, &[project_path.join("NARRATIVE")])
+}
+
+/// Collects the documents under the given folders, sorted by `order` then
+/// path. Orders come from the metadata cache, which skips unreadable files.
+fn ordered_paths(project_path: &Path, dirs: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut folders: Vec<PathBuf> = collect_document_paths(project_path)?
+        .into_iter()
+        .filter(|path| dirs.iter().any(|dir| path.starts_with(dir)))
+        .filter_map(|path| path.parent().map(Path::to_path_buf))
+        .collect();
+    folders.sort();
+    folders.dedup();
+
+    let mut entries = Vec::new();
+    for folder in folders {
+        match list_document_meta(&folder, DocumentSort::Order) {
+            Ok(metas) => entries.extend(
+                metas
+                    .into_iter()
+                    .map(|meta| (meta.order.unwrap_or(i64::MAX), PathBuf::from(meta.path))),
+            ),
+            Err(e) => eprintln!("Failed to list documents in {}: {}", folder.display(), e),
+        }
+    }
+
+    entries.sort();
+    Ok(entries.into_iter().map(|(_, path)| path).collect())
+}
+
+/// Reads documents in order, logging and skipping any that can't be read
+pub fn readable_documents(paths: &[PathBuf]) -> impl Iterator<Item = Document> + '_ {
+    paths.iter().filter_map(|path| match read_document(path) {
+        Ok(document) => Some(document),
+        Err(e) => {
+            eprintln!("Failed to read document {}: {}", path.display(), e);
+            None
+        }
+    })
+}
+
+/// Compiles the manuscript into a single in-memory string
+#[cfg(test)]
+pub fn compile_manuscript(
+    project_path: &Path,
+    format: ExportFormat,
+    resolve_links: bool,
+    config_path: &Path,
+) -> Result<String> {
+    let mut buffer = Vec::new();
+    write_manuscript(project_path, format, resolve_links, config_path, &mut buffer)?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+/// Streams the manuscript to a writer one document at a time. With
+/// `resolve_links`, wikilinks become their display text and relative image
+/// paths are made absolute; otherwise bodies are exported as written. The
+/// title page falls back to the author in `config_path`.
+pub fn write_manuscript<W: Write>(
+    project_path: &Path,
+    format: ExportFormat,
+    resolve_links: bool,
+    config_path: &Path,
+    writer: &mut W,
+) -> Result<()> {
+    let id_map = build_id_map(project_path)?;
+    let title = manuscript_title(project_path);
+    writer.write_all(render_header(&title, format).as_bytes())?;
+    let author = resolve_author_in(config_path, project_path);
+    writer.write_all(render_title_page(&title, author.as_deref(), format).as_bytes())?;
+
+    let paths = manuscript_paths(project_path)?;
+    for (index, document) in readable_documents(&paths).enumerate() {
+        if index > 0 {
+            writer.write_all(separator(format).as_bytes())?;
+        }
+        let body = prepare_body(&document, project_path, &id_map, resolve_links)?;
+        writer.write_all(render_body(&body, format).as_bytes())?;
+    }
+
+    writer.write_all(render_footer(format).as_bytes())?;
+    Ok(())
+}
+
+/// Exports the manuscript to a file, streaming documents to disk
+pub fn export_manuscript(
+    project_path: &Path,
+    output: &Path,
+    format: ExportFormat,
+    resolve_links: bool,
+) -> Result<PathBuf> {
+    export_manuscript_with(project_path, output, format, resolve_links, &config_path()?)
+}
+
+/// Exports the manuscript, taking the fallback author from the given config
+fn export_manuscript_with(
+    project_path: &Path,
+    output: &Path,
+    format: ExportFormat,
+    resolve_links: bool,
+    config_path: &Path,
+) -> Result<PathBuf> {
+    write_export_file(output, |writer| write_manuscript(project_path, format, resolve_links, config_path, writer))
+}
+
+/// Exports a single document as a standalone file
+pub fn export_document(document_path: &Path, output: &Path, format: ExportFormat) -> Result<PathBuf> {
+    let document = read_document(document_path)?;
+
+    // Links and images resolve against the project the document lives in
+    let project_path = find_project_root(document_path)
+        .or_else(|| document_path.parent().map(Path::to_path_buf))
+        .ok_or_else(|| anyhow::anyhow!("Invalid document path: {}", document_path.display()))?;
+    let id_map = build_id_map(&project_path)?;
+    let body = prepare_body(&document, &project_path, &id_map, true)?;
+
+    write_export_file(output, |writer| {
+        writer.write_all(render_header(&document.title, format).as_bytes())?;
+        writer.write_all(render_body(&body, format).as_bytes())?;
+        writer.write_all(render_footer(format).as_bytes())?;
+        Ok(())
+    })
+}
+
+/// Writes a Markdown outline of the NARRATIVE documents: title, synopsis,
+/// word count and status of each, in reading order
+pub fn export_outline(project_path: &Path, output: &Path) -> Result<PathBuf> {
+    let paths = narrative_paths(project_path)?;
+    let title = manuscript_title(project_path);
+
+    write_export_file(output, |writer| {
+        writeln!(writer, "# {} — Outline\n", title)?;
+
+        for (index, document) in readable_documents(&paths).enumerate() {
+            let field = |key: &str| {
+                document
+                    .metadata
+                    .as_ref()
+                    .and_then(|m| m.get(key))
+                    .and_then(|v| v.as_str())
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+                    .map(str::to_string)
+            };
+
+            writeln!(writer, "## {}. {}\n", index + 1, document.title)?;
+            writeln!(writer, "- Words: {}", document.word_count)?;
+            writeln!(writer, "- Status: {}", field("status").unwrap_or_else(|| "none".to_string()))?;
+            if let Some(synopsis) = field("synopsis").or_else(|| field("summary")) {
+                writeln!(writer, "\n{}", synopsis)?;
+            }
+            writeln!(writer)?;
+        }
+
+        Ok(())
+    })
+}
+
+/// Writes an export through a temp file so a failed export never leaves a partial file
+fn write_export_file<F>(output: &Path, write: F) -> Result<PathBuf>
+where
+    F: FnOnce(&mut BufWriter<File>) -> Result<()>,
+{
+    if let Some(parent) = output.parent() {
+        ensure_dir(parent)?;
+    }
+
+    // A unique hidden sibling, so no existing file can be clobbered
+    let file_name = output
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("Invalid export path: {}", output.display()))?;
+    let temp_path = output.with_file_name(format!(
+        ".{}.{}.tmp",
+        file_name.to_string_lossy(),
+        uuid::Uuid::new_v4()
+    ));
+    let file = File::create(&temp_path)
+        .with_context(|| format!("Failed to create export file: {:?}", temp_path))?;
+    let mut writer = BufWriter::new(file);
+
+    let result = write(&mut writer).and_then(|_| writer.flush().context("Failed to flush export file"));
+    if let Err(e) = result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e);
+    }
+    drop(writer);
+
+    fs::rename(&temp_path, output)
+        .with_context(|| format!("Failed to rename temp file to: {:?}", output))?;
+
+    Ok(output.to_path_buf())
+}
+
+/// Strips frontmatter and inlines transclusions in a document body, then
+/// optionally resolves wikilinks and image paths
+fn prepare_body(document: &Document, project_path: &Path, id_map: &IdMap, resolve_links: bool) -> Result<String> {
+    let document_path = Path::new(&document.path);
+    let document_dir = document_path.parent().unwrap_or(project_path);
+
+    let mut stack = vec![document_path.to_path_buf()];
+    let body = transclude(strip_frontmatter(&document.content), id_map, &mut stack)
+        .with_context(|| format!("Failed to export {}", document.title))?;
+    if !resolve_links {
+        return Ok(body);
+    }
+
+    let body = resolve_wikilinks(&body, id_map);
+
+    Ok(resolve_image_paths(&body, document_dir, project_path))
+}
+
+/// Uses the project name as the manuscript title, falling back to the folder name
+fn manuscript_title(project_path: &Path) -> String {
+    open_project(project_path)
+        .map(|project| project.name)
+        .unwrap_or_else(|_| {
+            project_path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .unwrap_or("Untitled")
+                .to_string()
+        })
+}
+
+/// Renders the opening of an export
+fn render_header(title: &str, format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Markdown => String::new(),
+        ExportFormat::Html => format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n",
+            escape_html(title)
+        ),
+    }
+}
+
+/// Renders the manuscript title page, with the author when one is configured
+fn render_title_page(title: &str, author: Option<&str>, format: ExportFormat) -> String {
+    match (format, author) {
+        (ExportFormat::Markdown, None) => format!("# {}\n\n", title),
+        (ExportFormat::Markdown, Some(author)) => format!("# {}\n\nby {}\n\n", title, author),
+        (ExportFormat::Html, None) => format!("<h1 class=\"title\">{}</h1>\n", escape_html(title)),
+        (ExportFormat::Html, Some(author)) => format!(
+            "<h1 class=\"title\">{}</h1>\n<p class=\"author\">by {}</p>\n",
+            escape_html(title),
+            escape_html(author)
+        ),
+    }
+}
+
+/// Renders a single document body
+fn render_body(body: &str, format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Markdown => body.trim().to_string(),
+        ExportFormat::Html => {
+            let mut rendered = String::from("<section>\n");
+            html::push_html(&mut rendered, Parser::new_ext(body, Options::all()));
+            rendered.push_str("</section>\n");
+            rendered
+        }
+    }
+}
+
+/// Separator written between two documents
+fn separator(format: ExportFormat) -> &'static str {
+    match format {
+        ExportFormat::Markdown => MARKDOWN_SEPARATOR,
+        ExportFormat::Html => "",
+    }
+}
+
+/// Renders the closing of an export
+fn render_footer(format: ExportFormat) -> &'static str {
+    match format {
+        ExportFormat::Markdown => "\n",
+        ExportFormat::Html => "</body>\n</html>\n",
+    }
+}
+
+/// Escapes text for use inside HTML
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::project_service::create_project;
+    use std::env;
+
+    #[test]
+    fn test_streaming_export_matches_expected_output() {
+        let temp_dir = env::temp_dir().join("aycd_export_test_stream");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let project = create_project("novel", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+        let drafts = project_path.join("NARRATIVE/Drafts");
+        fs::write(drafts.join("b.md"), "---\norder: 2\n---\n\n# Two\n\nSecond chapter.").unwrap();
+        fs::write(drafts.join("a.md"), "---\norder: 1\n---\n\n# One\n\nFirst chapter.").unwrap();
+        fs::write(project_path.join("NARRATIVE/Research/notes.md"), "Not exported").unwrap();
+        // Unreadable documents are skipped instead of failing the export
+        fs::write(drafts.join("broken.md"), [0xff, 0xfe, 0x00]).unwrap();
+        // An unrelated file that a naive "out.tmp" temp file would clobber
+        fs::write(temp_dir.join("out.tmp"), "keep me").unwrap();
+        let config = temp_dir.join("config.json");
+
+        let expected_markdown = "# novel\n\n# One\n\nFirst chapter.\n\n* * *\n\n# Two\n\nSecond chapter.\n";
+        let expected_html = concat!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>novel</title>\n</head>\n<body>\n",
+            "<h1 class=\"title\">novel</h1>\n",
+            "<section>\n<h1>One</h1>\n<p>First chapter.</p>\n</section>\n",
+            "<section>\n<h1>Two</h1>\n<p>Second chapter.</p>\n</section>\n",
+            "</body>\n</html>\n",
+        );
+
+        for (format, file_name, expected) in [
+            (ExportFormat::Markdown, "out.md", expected_markdown),
+            (ExportFormat::Html, "out.html", expected_html),
+        ] {
+            let output = temp_dir.join(file_name);
+            export_manuscript_with(&project_path, &output, format, false, &config).unwrap();
+            assert_eq!(fs::read_to_string(&output).unwrap(), expected);
+        }
+        assert_eq!(fs::read_to_string(temp_dir.join("out.tmp")).unwrap(), "keep me");
+        let leftovers = fs::read_dir(&temp_dir)
+            .unwrap()
+            .filter(|entry| entry.as_ref().unwrap().file_name().to_string_lossy().starts_with('.'))
+            .count();
+        assert_eq!(leftovers, 0);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_manuscript_title_page_author() {
+        let temp_dir = env::temp_dir().join("aycd_export_test_author");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let mut project = create_project("novel", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+        fs::write(project_path.join("NARRATIVE/Drafts/One.md"), "Once upon a time.").unwrap();
+        let config = temp_dir.join("config.json");
+        let output = temp_dir.join("out.md");
+
+        export_manuscript_with(&project_path, &output, ExportFormat::Markdown, false, &config).unwrap();
+        assert!(!fs::read_to_string(&output).unwrap().contains("by "));
+
+        // The app-wide author is used when the project has none
+        fs::write(&config, r#"{"author": "App Default"}"#).unwrap();
+        export_manuscript_with(&project_path, &output, ExportFormat::Markdown, false, &config).unwrap();
+        assert!(fs::read_to_string(&output).unwrap().starts_with("# novel\n\nby App Default\n\n"));
+
+        project.settings.author = Some("Jane <Writer>".to_string());
+        crate::services::project_service::update_project(&project).unwrap();
+        let html_output = temp_dir.join("out.html");
+        export_manuscript_with(&project_path, &html_output, ExportFormat::Html, false, &config).unwrap();
+        let html = fs::read_to_string(&html_output).unwrap();
+        assert!(html.contains("<p class=\"author\">by Jane &lt;Writer&gt;</p>"));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_manuscript_link_resolution_is_opt_in() {
+        let temp_dir = env::temp_dir().join("aycd_export_test_links");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let project = create_project("novel", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+        fs::write(project_path.join("WORLD/Cast/Anna.md"), "---\nid: anna-1\ntitle: Anna\n---\n\nHero").unwrap();
+        fs::write(
+            project_path.join("NARRATIVE/Drafts/One.md"),
+            "---\ntitle: One\n---\n\n[[anna-1]] waves. ![map](map.png)",
+        )
+        .unwrap();
+
+        let config = temp_dir.join("config.json");
+        let plain = compile_manuscript(&project_path, ExportFormat::Markdown, false, &config).unwrap();
+        assert!(plain.contains("[[anna-1]] waves. ![map](map.png)"));
+
+        let resolved = compile_manuscript(&project_path, ExportFormat::Markdown, true, &config).unwrap();
+        assert!(resolved.contains("Anna waves."));
+        let expected_image = project_path.join("map.png").to_string_lossy().replace('\\', "/");
+        assert!(resolved.contains(&format!("![map]({})", expected_image)));
+
+        let output = temp_dir.join("out.md");
+        export_manuscript_with(&project_path, &output, ExportFormat::Markdown, true, &config).unwrap();
+        assert_eq!(fs::read_to_string(&output).unwrap(), resolved);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_export_single_document_to_html() {
+        let temp_dir = env::temp_dir().join("aycd_export_test_single");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let project = create_project("novel", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+        fs::write(
+            project_path.join("WORLD/Cast/Anna.md"),
+            "---\nid: anna-1\ntitle: Anna\n---\n\n# Anna\n\nSister of [[Ben]], lives in [[Town|the town]].",
+        )
+        .unwrap();
+        fs::write(project_path.join("WORLD/Cast/Ben.md"), "---\nid: ben-1\ntitle: Benjamin\n---\n\nBrother").unwrap();
+
+        let output = temp_dir.join("exports/anna.html");
+        export_document(&project_path.join("WORLD/Cast/Anna.md"), &output, ExportFormat::Html).unwrap();
+
+        let html = fs::read_to_string(&output).unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Anna</title>"));
+        assert!(html.contains("<h1>Anna</h1>"));
+        assert!(html.contains("Sister of Benjamin, lives in the town."));
+        assert!(!html.contains("id: anna-1"));
+        assert!(!html.contains("Brother"));
+        assert!(html.ends_with("</html>\n"));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_transclusion_on_export() {
+        let temp_dir = env::temp_dir().join("aycd_export_test_transclusion");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let project = create_project("novel", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+        let drafts = project_path.join("NARRATIVE/Drafts");
+        fs::write(drafts.join("Chapter.md"), "---\ntitle: Chapter\n---\n\nBefore.\n\n![[Prologue]]\n\nAfter.").unwrap();
+        fs::write(
+            project_path.join("NARRATIVE/Planning/Prologue.md"),
+            "---\nid: prologue-1\ntitle: Prologue\n---\n\nIt was a dark night.",
+        )
+        .unwrap();
+
+        let config = temp_dir.join("config.json");
+        let markdown = compile_manuscript(&project_path, ExportFormat::Markdown, false, &config).unwrap();
+        assert!(markdown.contains("Before.\n\nIt was a dark night.\n\nAfter."));
+        assert!(!markdown.contains("![["));
+
+        // A document embedding itself is rejected instead of recursing forever
+        let selfish = drafts.join("Selfish.md");
+        fs::write(&selfish, "---\nid: selfish-1\n---\n\nLoop: ![[selfish-1]]").unwrap();
+        let error = export_document(&selfish, &temp_dir.join("selfish.md"), ExportFormat::Markdown).unwrap_err();
+        assert!(format!("{:#}", error).contains("cycle"));
+        assert!(!temp_dir.join("selfish.md").exists());
+        assert!(compile_manuscript(&project_path, ExportFormat::Markdown, false, &config).is_err());
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_export_outline() {
+        let temp_dir = env::temp_dir().join("aycd_export_test_outline");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let project = create_project("novel", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+        fs::write(
+            project_path.join("NARRATIVE/Drafts/Two.md"),
+            "---\ntitle: Two\norder: 2\nstatus: draft\n---\n\nfour words right here",
+        )
+        .unwrap();
+        fs::write(
+            project_path.join("NARRATIVE/Final/One.md"),
+            "---\ntitle: One\norder: 1\nstatus: final\nsynopsis: Anna leaves home.\n---\n\nthree words here",
+        )
+        .unwrap();
+        fs::write(project_path.join("WORLD/Cast/Anna.md"), "---\ntitle: Anna\n---\n\nNot narrative").unwrap();
+
+        let output = export_outline(&project_path, &temp_dir.join("outline.md")).unwrap();
+        let outline = fs::read_to_string(output).unwrap();
+
+        assert_eq!(outline.matches("\n## ").count(), 2);
+        assert!(outline.contains("## 1. One\n\n- Words: 3\n- Status: final\n\nAnna leaves home."));
+        assert!(outline.contains("## 2. Two\n\n- Words: 4\n- Status: draft\n"));
+        assert!(!outline.contains("Anna\n"));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+}
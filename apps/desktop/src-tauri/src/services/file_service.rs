@@ -1,6 +1,12 @@
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::time::SystemTime;
+
+/// One lock per directory, shared by every caller in the process
+static DIR_LOCKS: OnceLock<Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>> = OnceLock::new();
 
 /// Validates that a path is safe and within allowed boundaries
 pub fn validate_path(path: &Path, base_path: &Path) -> Result<PathBuf> {
@@ -34,7 +40,15 @@ pub fn read_file(path: &Path) -> Result<String> {
 
 /// Writes a file atomically (write to temp, then rename)
 pub fn write_file(path: &Path, content: &str) -> Result<()> {
-    let temp_path = path.with_extension("tmp");
+    // A unique hidden sibling, so concurrent writers never share a temp file
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("Invalid file path: {:?}", path))?;
+    let temp_path = path.with_file_name(format!(
+        ".{}.{}.tmp",
+        file_name.to_string_lossy(),
+        uuid::Uuid::new_v4()
+    ));
 
     fs::write(&temp_path, content)
         .with_context(|| format!("Failed to write temp file: {:?}", temp_path))?;
@@ -45,6 +59,57 @@ pub fn write_file(path: &Path, content: &str) -> Result<()> {
     Ok(())
 }
 
+/// Updates a file's modification time to now without changing its content
+pub fn touch_file(path: &Path) -> Result<()> {
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .open(path)
+        .with_context(|| format!("Failed to open file: {:?}", path))?;
+
+    file.set_modified(SystemTime::now())
+        .with_context(|| format!("Failed to update modification time: {:?}", path))?;
+
+    Ok(())
+}
+
+/// Checks whether a path can be written to. Directories are probed with a
+/// temporary file; missing paths are writable if their nearest existing
+/// ancestor is a writable directory.
+pub fn is_writable(path: &Path) -> bool {
+    if path.is_dir() {
+        let probe = path.join(format!(".aycd-write-test-{}", uuid::Uuid::new_v4()));
+        let writable = fs::File::create(&probe).is_ok();
+        let _ = fs::remove_file(&probe);
+        return writable;
+    }
+
+    if path.exists() {
+        return fs::OpenOptions::new().append(true).open(path).is_ok();
+    }
+
+    path.ancestors()
+        .skip(1)
+        .find(|ancestor| ancestor.exists())
+        .is_some_and(|ancestor| ancestor.is_dir() && is_writable(ancestor))
+}
+
+/// Runs `f` while holding the process-wide lock for a directory, so
+/// read-modify-write cycles on files inside it (like a project's caches)
+/// can't interleave. `f` must not take the same lock again.
+pub fn with_dir_lock<T>(dir: &Path, f: impl FnOnce() -> T) -> T {
+    let key = fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+    let lock = DIR_LOCKS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .entry(key)
+        .or_default()
+        .clone();
+
+    let _guard = lock.lock().unwrap_or_else(PoisonError::into_inner);
+    f()
+}
+
 /// Deletes a file safely
 pub fn delete_file(path: &Path) -> Result<()> {
     if path.exists() {
@@ -116,8 +181,28 @@ mod tests {
         let read_content = read_file(&temp_file).unwrap();
 
         assert_eq!(content, read_content);
+        assert!(!temp_file.with_extension("tmp").exists());
 
         delete_file(&temp_file).unwrap();
         assert!(!temp_file.exists());
     }
+
+    #[test]
+    fn test_is_writable() {
+        let temp_dir = env::temp_dir().join("aycd_test_writable");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        assert!(is_writable(&temp_dir));
+        assert!(is_writable(&temp_dir.join("missing/child")));
+        assert_eq!(fs::read_dir(&temp_dir).unwrap().count(), 0);
+
+        // Nothing can be created underneath a regular file
+        let file = temp_dir.join("file.txt");
+        fs::write(&file, "x").unwrap();
+        assert!(is_writable(&file));
+        assert!(!is_writable(&file.join("child")));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
 }
@@ -0,0 +1,297 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::document_service::{collect_document_paths, read_document, strip_frontmatter};
+use super::file_service::read_file;
+
+/// A document known to the id map
+#[derive(Debug, Clone)]
+pub struct IdMapEntry {
+    pub id: String,
+    pub title: String,
+    pub path: PathBuf,
+}
+
+/// Lookup table resolving wikilink targets (ids, titles or file names) to documents
+#[derive(Debug, Default)]
+pub struct IdMap {
+    entries: Vec<IdMapEntry>,
+    by_id: HashMap<String, usize>,
+    by_name: HashMap<String, usize>,
+}
+
+impl IdMap {
+    /// Resolves a link target by id first, then by title or file name (case-insensitive)
+    pub fn resolve(&self, target: &str) -> Option<&IdMapEntry> {
+        let target = target.trim();
+        self.by_id
+            .get(target)
+            .or_else(|| self.by_name.get(&target.to_lowercase()))
+            .map(|&index| &self.entries[index])
+    }
+
+    /// Looks up a document by its id only
+    pub fn get(&self, id: &str) -> Option<&IdMapEntry> {
+        self.by_id.get(id.trim()).map(|&index| &self.entries[index])
+    }
+
+    /// All documents in the map
+    pub fn entries(&self) -> &[IdMapEntry] {
+        &self.entries
+    }
+}
+
+/// Builds the id map for every document in a project
+pub fn build_id_map(project_path: &Path) -> Result<IdMap> {
+    let mut map = IdMap::default();
+
+    for path in collect_document_paths(project_path)? {
+        let doc = match read_document(&path) {
+            Ok(doc) => doc,
+            Err(e) => {
+                eprintln!("Failed to read document {}: {}", path.display(), e);
+                continue;
+            }
+        };
+
+        let index = map.entries.len();
+        if !doc.id.is_empty() {
+            map.by_id.insert(doc.id.clone(), index);
+        }
+        map.by_name.entry(doc.title.to_lowercase()).or_insert(index);
+        if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+            map.by_name.entry(stem.to_lowercase()).or_insert(index);
+        }
+
+        map.entries.push(IdMapEntry {
+            id: doc.id,
+            title: doc.title,
+            path,
+        });
+    }
+
+    Ok(map)
+}
+
+/// A `[[target|label]]` wikilink (or `![[target]]` embed) found in text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WikiLink {
+    /// Byte range of the whole link, including a leading `!` for embeds
+    pub start: usize,
+    pub end: usize,
+    pub target: String,
+    pub label: Option<String>,
+    pub embed: bool,
+}
+
+/// Finds all wikilinks in a piece of text
+pub fn find_wikilinks(text: &str) -> Vec<WikiLink> {
+    let mut links = Vec::new();
+    let mut offset = 0;
+
+    while let Some(open) = text[offset..].find("[[") {
+        let open = offset + open;
+        let Some(close) = text[open + 2..].find("]]") else {
+            break;
+        };
+        let close = open + 2 + close;
+        let inner = &text[open + 2..close];
+
+        // Links never span lines; skip a stray "[[" and keep scanning
+        if inner.contains('\n') || inner.trim().is_empty() {
+            offset = open + 2;
+            continue;
+        }
+
+        let (target, label) = match inner.split_once('|') {
+            Some((target, label)) => (target.trim(), Some(label.trim().to_string())),
+            None => (inner.trim(), None),
+        };
+        let embed = open > 0 && text.as_bytes()[open - 1] == b'!';
+
+        links.push(WikiLink {
+            start: if embed { open - 1 } else { open },
+            end: close + 2,
+            target: target.to_string(),
+            label,
+            embed,
+        });
+        offset = close + 2;
+    }
+
+    links
+}
+
+/// Replaces wikilinks with readable text: the label, the resolved document
+/// title, or the raw target when it can't be resolved. Embeds are left as-is.
+pub fn resolve_wikilinks(text: &str, id_map: &IdMap) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut last = 0;
+
+    for link in find_wikilinks(text).into_iter().filter(|l| !l.embed) {
+        output.push_str(&text[last..link.start]);
+        let display = link
+            .label
+            .clone()
+            .or_else(|| id_map.resolve(&link.target).map(|entry| entry.title.clone()))
+            .unwrap_or_else(|| link.target.clone());
+        output.push_str(&display);
+        last = link.end;
+    }
+
+    output.push_str(&text[last..]);
+    output
+}
+
+/// Inlines `![[Other Doc]]` embeds with the referenced document's body,
+/// recursively. `stack` holds the documents currently being expanded so a
+/// cycle (including a document embedding itself) is reported as an error.
+/// Embeds that don't resolve to a document are left untouched.
+pub fn transclude(text: &str, id_map: &IdMap, stack: &mut Vec<PathBuf>) -> Result<String> {
+    let mut output = String::with_capacity(text.len());
+    let mut last = 0;
+
+    for link in find_wikilinks(text).into_iter().filter(|l| l.embed) {
+        let Some(entry) = id_map.resolve(&link.target) else {
+            continue;
+        };
+
+        if stack.contains(&entry.path) {
+            let chain: Vec<String> = stack
+                .iter()
+                .chain(std::iter::once(&entry.path))
+                .map(|p| p.file_stem().and_then(|s| s.to_str()).unwrap_or("?").to_string())
+                .collect();
+            anyhow::bail!("Transclusion cycle detected: {}", chain.join(" -> "));
+        }
+
+        let content = read_file(&entry.path)?;
+        stack.push(entry.path.clone());
+        let inlined = transclude(strip_frontmatter(&content).trim(), id_map, stack);
+        stack.pop();
+
+        output.push_str(&text[last..link.start]);
+        output.push_str(&inlined?);
+        last = link.end;
+    }
+
+    output.push_str(&text[last..]);
+    Ok(output)
+}
+
+/// Rewrites the target of each wikilink for which `rewrite` returns a new
+/// target, keeping labels and embeds intact. Returns the text and the number
+/// of links rewritten.
+pub fn rewrite_wikilink_targets<F>(text: &str, rewrite: F) -> (String, usize)
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut output = String::with_capacity(text.len());
+    let mut last = 0;
+    let mut rewritten = 0;
+
+    for link in find_wikilinks(text) {
+        let Some(target) = rewrite(&link.target) else {
+            continue;
+        };
+
+        output.push_str(&text[last..link.start]);
+        if link.embed {
+            output.push('!');
+        }
+        match &link.label {
+            Some(label) => output.push_str(&format!("[[{}|{}]]", target, label)),
+            None => output.push_str(&format!("[[{}]]", target)),
+        }
+        last = link.end;
+        rewritten += 1;
+    }
+
+    output.push_str(&text[last..]);
+    (output, rewritten)
+}
+
+/// Rewrites relative Markdown image paths to absolute paths so exported files
+/// still find them. Paths are resolved against the document's folder, then
+/// the project root.
+pub fn resolve_image_paths(text: &str, document_dir: &Path, project_path: &Path) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut last = 0;
+    let mut offset = 0;
+
+    while let Some(start) = text[offset..].find("![") {
+        let start = offset + start;
+        offset = start + 2;
+
+        // `![[...]]` is an embed, not an image
+        if text[offset..].starts_with('[') {
+            continue;
+        }
+        let Some(alt_end) = text[offset..].find("](") else {
+            break;
+        };
+        let src_start = offset + alt_end + 2;
+        let Some(src_len) = text[src_start..].find(')') else {
+            break;
+        };
+        let src_end = src_start + src_len;
+        offset = src_end + 1;
+
+        // The path is `<...>` or runs up to an optional `"title"`
+        let destination = &text[src_start..src_end];
+        let rest = destination.trim_start();
+        let rest_start = src_start + destination.len() - rest.len();
+        let (path_start, path_len) = match rest.strip_prefix('<') {
+            Some(inner) => match inner.find('>') {
+                Some(len) => (rest_start + 1, len),
+                None => continue,
+            },
+            None => (rest_start, rest.find(char::is_whitespace).unwrap_or(rest.len())),
+        };
+        let src = &text[path_start..path_start + path_len];
+
+        if src.is_empty() || src.contains("://") || src.starts_with("data:") || Path::new(src).is_absolute() {
+            continue;
+        }
+
+        let local = document_dir.join(src);
+        let resolved = if local.exists() { local } else { project_path.join(src) };
+
+        output.push_str(&text[last..path_start]);
+        output.push_str(&resolved.to_string_lossy().replace('\\', "/"));
+        last = path_start + path_len;
+    }
+
+    output.push_str(&text[last..]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_wikilinks() {
+        let links = find_wikilinks("See [[Anna|her]] and ![[Map]] or [[broken");
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].target, "Anna");
+        assert_eq!(links[0].label.as_deref(), Some("her"));
+        assert!(!links[0].embed);
+        assert_eq!(links[1].target, "Map");
+        assert!(links[1].embed);
+        assert_eq!(&"See [[Anna|her]] and ![[Map]] or [[broken"[links[1].start..links[1].end], "![[Map]]");
+    }
+
+    #[test]
+    fn test_resolve_image_paths() {
+        let text = "![map](images/map.png) ![web](https://example.com/a.png)";
+        let resolved = resolve_image_paths(text, Path::new("/p/WORLD/Places"), Path::new("/p"));
+        assert_eq!(resolved, "![map](/p/images/map.png) ![web](https://example.com/a.png)");
+
+        // Optional titles and `<...>` destinations keep their syntax
+        let text = "![map](images/map.png \"The map\") ![key](<images/map key.png>)";
+        let resolved = resolve_image_paths(text, Path::new("/p/WORLD/Places"), Path::new("/p"));
+        assert_eq!(resolved, "![map](/p/images/map.png \"The map\") ![key](</p/images/map key.png>)");
+    }
+}
@@ -0,0 +1,436 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::models::{Document, DocumentMeta, DocumentSort};
+use super::document_service::{collect_document_paths, parse_document, read_document};
+use super::file_service::{ensure_dir, read_file, with_dir_lock, write_file};
+use super::project_service::find_project_root;
+
+/// A cached document entry, valid while the file's mtime and size match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheEntry {
+    mtime_ns: u64,
+    size: u64,
+    meta: DocumentMeta,
+    /// Full frontmatter, for project-wide key listings. Entries written before
+    /// it was cached don't have it and are treated as stale for those.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    frontmatter: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+/// Per-project cache of document metadata, stored in cache/meta.json
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct MetaCache {
+    entries: HashMap<String, CacheEntry>,
+}
+
+/// Location of the metadata cache inside a project
+fn cache_path(project_path: &Path) -> PathBuf {
+    project_path.join("cache").join("meta.json")
+}
+
+/// Loads a project's cache; a missing or unreadable cache starts empty
+fn load_cache(project_path: &Path) -> MetaCache {
+    read_file(&cache_path(project_path))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Runs a load-modify-save cycle on the cache of the project containing
+/// `dir_path` under the project lock. Folders outside a project lock only
+/// themselves and get an empty cache that is never saved.
+fn with_project_cache<T>(dir_path: &Path, f: impl FnOnce(Option<&Path>, &mut MetaCache) -> T) -> T {
+    let project_path = find_project_root(dir_path);
+    let lock_path = project_path.as_deref().unwrap_or(dir_path);
+
+    with_dir_lock(lock_path, || {
+        let mut cache = project_path.as_deref().map(load_cache).unwrap_or_default();
+        f(project_path.as_deref(), &mut cache)
+    })
+}
+
+/// Saves a project's cache
+fn save_cache(project_path: &Path, cache: &MetaCache) -> Result<()> {
+    ensure_dir(&project_path.join("cache"))?;
+    let json = serde_json::to_string(cache).context("Failed to serialize metadata cache")?;
+    write_file(&cache_path(project_path), &json)
+}
+
+/// Reads the file's modification time (nanoseconds) and size
+fn file_stamp(path: &Path) -> Result<(u64, u64)> {
+    let metadata = fs::metadata(path).with_context(|| format!("Failed to read metadata: {:?}", path))?;
+    Ok(metadata_stamp(&metadata))
+}
+
+/// Modification time (nanoseconds) and size from already-read file metadata
+fn metadata_stamp(metadata: &fs::Metadata) -> (u64, u64) {
+    let mtime_ns = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    (mtime_ns, metadata.len())
+}
+
+/// The cached entry for a file, if it's still valid for the given stamp
+fn fresh_entry<'a>(cache: &'a MetaCache, key: &str, (mtime_ns, size): (u64, u64)) -> Option<&'a CacheEntry> {
+    cache
+        .entries
+        .get(key)
+        .filter(|entry| entry.mtime_ns == mtime_ns && entry.size == size)
+}
+
+/// Forgets cached files of a directory that no longer exist in it. Returns
+/// true when anything was removed.
+fn forget_missing(cache: &mut MetaCache, dir_path: &Path, files: &[PathBuf]) -> bool {
+    let before = cache.entries.len();
+    cache.entries.retain(|key, _| {
+        let path = Path::new(key);
+        path.parent() != Some(dir_path) || files.iter().any(|f| f == path)
+    });
+    cache.entries.len() != before
+}
+
+/// Extracts the cached values from a fully read document
+fn document_meta(document: &Document) -> DocumentMeta {
+    let frontmatter = document.metadata.as_ref();
+    DocumentMeta {
+        path: document.path.clone(),
+        id: document.id.clone(),
+        title: document.title.clone(),
+        document_type: document.document_type.clone(),
+        word_count: document.word_count,
+        status: frontmatter
+            .and_then(|m| m.get("status"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        order: frontmatter.and_then(|m| m.get("order")).and_then(|v| v.as_i64()),
+        modified_at: document.modified_at,
+    }
+}
+
+/// Builds the cache entry for a freshly read document
+fn cache_entry(document: &Document, (mtime_ns, size): (u64, u64)) -> CacheEntry {
+    let frontmatter = document
+        .metadata
+        .as_ref()
+        .and_then(|m| m.as_object())
+        .cloned()
+        .unwrap_or_default();
+
+    CacheEntry {
+        mtime_ns,
+        size,
+        meta: document_meta(document),
+        frontmatter: Some(frontmatter),
+    }
+}
+
+/// Markdown files directly inside a directory
+fn markdown_files(dir_path: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir_path).with_context(|| format!("Failed to read directory: {:?}", dir_path))? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("md") {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// Brings the cache up to date for one directory. Returns the directory's
+/// metadata, how many files had to be re-read and whether the cache changed.
+fn refresh_dir(cache: &mut MetaCache, dir_path: &Path) -> Result<(Vec<DocumentMeta>, usize, bool)> {
+    let files = markdown_files(dir_path)?;
+    let mut metas = Vec::with_capacity(files.len());
+    let mut reread = 0;
+
+    for path in &files {
+        let key = path.to_string_lossy().to_string();
+        let (mtime_ns, size) = file_stamp(path)?;
+
+        if let Some(entry) = fresh_entry(cache, &key, (mtime_ns, size)) {
+            metas.push(entry.meta.clone());
+            continue;
+        }
+
+        match read_document(path) {
+            Ok(document) => {
+                let entry = cache_entry(&document, (mtime_ns, size));
+                metas.push(entry.meta.clone());
+                cache.entries.insert(key, entry);
+                reread += 1;
+            }
+            Err(e) => eprintln!("Failed to read document {}: {}", path.display(), e),
+        }
+    }
+
+    let removed = forget_missing(cache, dir_path, &files);
+    Ok((metas, reread, reread > 0 || removed))
+}
+
+/// Sorts metadata in place
+fn sort_metas(metas: &mut [DocumentMeta], sort: DocumentSort) {
+    let by_title = |a: &DocumentMeta, b: &DocumentMeta| a.title.to_lowercase().cmp(&b.title.to_lowercase());
+    match sort {
+        DocumentSort::Modified => metas.sort_by_key(|m| std::cmp::Reverse(m.modified_at)),
+        DocumentSort::Title => metas.sort_by(by_title),
+        DocumentSort::WordCount => metas.sort_by_key(|m| std::cmp::Reverse(m.word_count)),
+        DocumentSort::Order => metas.sort_by(|a, b| {
+            a.order.unwrap_or(i64::MAX).cmp(&b.order.unwrap_or(i64::MAX)).then_with(|| by_title(a, b))
+        }),
+        DocumentSort::Status => metas.sort_by(|a, b| a.status.cmp(&b.status).then_with(|| by_title(a, b))),
+    }
+}
+
+/// Lists a directory's document metadata, re-reading only files changed since
+/// they were cached. Returns the number of files re-read alongside.
+fn list_document_meta_counted(dir_path: &Path, sort: DocumentSort) -> Result<(Vec<DocumentMeta>, usize)> {
+    if !dir_path.exists() {
+        return Ok((Vec::new(), 0));
+    }
+
+    let (mut metas, reread) = with_project_cache(dir_path, |project_path, cache| -> Result<_> {
+        let (metas, reread, changed) = refresh_dir(cache, dir_path)?;
+        if let Some(project_path) = project_path.filter(|_| changed) {
+            save_cache(project_path, cache)?;
+        }
+        Ok((metas, reread))
+    })?;
+
+    sort_metas(&mut metas, sort);
+    Ok((metas, reread))
+}
+
+/// Lists a directory's document metadata in the requested order, served from
+/// the project's metadata cache
+pub fn list_document_meta(dir_path: &Path, sort: DocumentSort) -> Result<Vec<DocumentMeta>> {
+    list_document_meta_counted(dir_path, sort).map(|(metas, _)| metas)
+}
+
+/// Reads one document, reusing the cached word count when the file hasn't
+/// changed. Returns the document and whether it had to be counted again.
+fn read_document_cached(cache: &mut MetaCache, path: &Path) -> Result<(Document, bool)> {
+    let key = path.to_string_lossy().to_string();
+    let metadata = fs::metadata(path).with_context(|| format!("Failed to read metadata: {:?}", path))?;
+    let content = read_file(path)?;
+    let (mtime_ns, size) = metadata_stamp(&metadata);
+
+    if let Some(entry) = fresh_entry(cache, &key, (mtime_ns, size)) {
+        let word_count = entry.meta.word_count;
+        return Ok((parse_document(path, content, &metadata, Some(word_count)), false));
+    }
+
+    let document = parse_document(path, content, &metadata, None);
+    cache.entries.insert(key, cache_entry(&document, (mtime_ns, size)));
+    Ok((document, true))
+}
+
+/// Reads a directory's documents, reusing cached metrics for unchanged files.
+/// Returns the number of files whose metrics were recomputed alongside.
+fn list_documents_counted(dir_path: &Path) -> Result<(Vec<Document>, usize)> {
+    if !dir_path.exists() {
+        return Ok((Vec::new(), 0));
+    }
+
+    let files = markdown_files(dir_path)?;
+
+    Ok(with_project_cache(dir_path, |project_path, cache| {
+        let mut documents = Vec::with_capacity(files.len());
+        let mut recounted = 0;
+
+        for path in &files {
+            match read_document_cached(cache, path) {
+                Ok((document, counted)) => {
+                    documents.push(document);
+                    recounted += usize::from(counted);
+                }
+                Err(e) => eprintln!("Failed to read document {}: {}", path.display(), e),
+            }
+        }
+
+        // Only write the cache back when an entry was added, changed or removed
+        let removed = forget_missing(cache, dir_path, &files);
+        if let Some(project_path) = project_path.filter(|_| recounted > 0 || removed) {
+            if let Err(e) = save_cache(project_path, cache) {
+                eprintln!("Failed to save metadata cache: {}", e);
+            }
+        }
+
+        (documents, recounted)
+    }))
+}
+
+/// Reads a directory's documents (unsorted), served through the metadata
+/// cache so unchanged files skip recomputing their word counts
+pub fn list_documents(dir_path: &Path) -> Result<Vec<Document>> {
+    list_documents_counted(dir_path).map(|(documents, _)| documents)
+}
+
+/// Frontmatter of every document in a project, re-reading only files changed
+/// since they were cached. Returns the number of files re-read alongside.
+fn project_frontmatter_counted(
+    project_path: &Path,
+) -> Result<(Vec<serde_json::Map<String, serde_json::Value>>, usize)> {
+    let paths = collect_document_paths(project_path)?;
+
+    with_dir_lock(project_path, || {
+        let mut cache = load_cache(project_path);
+        let mut frontmatters = Vec::new();
+        let mut reread = 0;
+
+        for path in paths {
+            let key = path.to_string_lossy().to_string();
+            let stamp = match file_stamp(&path) {
+                Ok(stamp) => stamp,
+                Err(e) => {
+                    eprintln!("Failed to read document {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+
+            if let Some(frontmatter) = fresh_entry(&cache, &key, stamp).and_then(|entry| entry.frontmatter.as_ref()) {
+                frontmatters.push(frontmatter.clone());
+                continue;
+            }
+
+            match read_document(&path) {
+                Ok(document) => {
+                    let entry = cache_entry(&document, stamp);
+                    frontmatters.push(entry.frontmatter.clone().unwrap_or_default());
+                    cache.entries.insert(key, entry);
+                    reread += 1;
+                }
+                Err(e) => eprintln!("Failed to read document {}: {}", path.display(), e),
+            }
+        }
+
+        if reread > 0 {
+            save_cache(project_path, &cache)?;
+        }
+
+        Ok((frontmatters, reread))
+    })
+}
+
+/// Frontmatter of every document in a project, served from the metadata cache
+pub fn project_frontmatter(project_path: &Path) -> Result<Vec<serde_json::Map<String, serde_json::Value>>> {
+    project_frontmatter_counted(project_path).map(|(frontmatters, _)| frontmatters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::project_service::create_project;
+    use std::env;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_sorting_uses_cache_for_unchanged_files() {
+        let temp_dir = env::temp_dir().join("aycd_meta_cache_test");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let project = create_project("novel", Some(temp_dir.clone())).unwrap();
+        let drafts = PathBuf::from(&project.path).join("NARRATIVE/Drafts");
+        fs::write(drafts.join("a.md"), "---\norder: 2\n---\n\none two three").unwrap();
+        fs::write(drafts.join("b.md"), "---\norder: 1\n---\n\none").unwrap();
+        fs::write(drafts.join("c.md"), "one two").unwrap();
+
+        let (metas, reread) = list_document_meta_counted(&drafts, DocumentSort::Order).unwrap();
+        assert_eq!(reread, 3);
+        assert!(metas[0].path.ends_with("b.md"));
+        assert!(metas[1].path.ends_with("a.md"));
+
+        // A different sort order is served entirely from the cache
+        let (metas, reread) = list_document_meta_counted(&drafts, DocumentSort::WordCount).unwrap();
+        assert_eq!(reread, 0);
+        let counts: Vec<usize> = metas.iter().map(|m| m.word_count).collect();
+        assert_eq!(counts, vec![3, 2, 1]);
+
+        // Only the changed file is read again
+        fs::write(drafts.join("c.md"), "one two three four five").unwrap();
+        let file = fs::OpenOptions::new().write(true).open(drafts.join("c.md")).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(5)).unwrap();
+        let (metas, reread) = list_document_meta_counted(&drafts, DocumentSort::WordCount).unwrap();
+        assert_eq!(reread, 1);
+        assert!(metas[0].path.ends_with("c.md"));
+
+        // Full listings keep the cache warm
+        fs::write(drafts.join("d.md"), "new").unwrap();
+        let (documents, recounted) = list_documents_counted(&drafts).unwrap();
+        assert_eq!((documents.len(), recounted), (4, 1));
+        let (metas, reread) = list_document_meta_counted(&drafts, DocumentSort::Title).unwrap();
+        assert_eq!(reread, 0);
+        assert_eq!(metas.len(), 4);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_listing_unchanged_folder_serves_cache_without_writing() {
+        let temp_dir = env::temp_dir().join("aycd_meta_cache_test_listing");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let project = create_project("novel", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+        let drafts = project_path.join("NARRATIVE/Drafts");
+        fs::write(drafts.join("a.md"), "---\ntitle: A\n---\n\none two three").unwrap();
+        fs::write(drafts.join("b.md"), "one").unwrap();
+
+        let (first, recounted) = list_documents_counted(&drafts).unwrap();
+        assert_eq!(recounted, 2);
+
+        // Push the cache's mtime into the past so a rewrite would be visible
+        let cache_file = cache_path(&project_path);
+        let past = SystemTime::now() - Duration::from_secs(3600);
+        fs::OpenOptions::new().write(true).open(&cache_file).unwrap().set_modified(past).unwrap();
+
+        let (second, recounted) = list_documents_counted(&drafts).unwrap();
+        assert_eq!(recounted, 0);
+        assert_eq!(fs::metadata(&cache_file).unwrap().modified().unwrap(), past);
+
+        let words = |docs: &[Document]| {
+            let mut words: Vec<(String, usize)> = docs.iter().map(|d| (d.path.clone(), d.word_count)).collect();
+            words.sort();
+            words
+        };
+        assert_eq!(words(&first), words(&second));
+        assert!(second.iter().any(|d| d.content.contains("one two three")));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_project_frontmatter_rereads_only_changed_files() {
+        let temp_dir = env::temp_dir().join("aycd_meta_cache_test_frontmatter");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let project = create_project("novel", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+        fs::write(project_path.join("WORLD/Cast/Anna.md"), "---\npov: true\n---\n\nHero").unwrap();
+        fs::write(project_path.join("NARRATIVE/Drafts/One.md"), "---\nstatus: draft\n---\n\nText").unwrap();
+
+        let (frontmatters, reread) = project_frontmatter_counted(&project_path).unwrap();
+        assert_eq!((frontmatters.len(), reread), (2, 2));
+
+        let (_, reread) = project_frontmatter_counted(&project_path).unwrap();
+        assert_eq!(reread, 0);
+
+        let one = project_path.join("NARRATIVE/Drafts/One.md");
+        fs::write(&one, "---\nstatus: final\n---\n\nText").unwrap();
+        let file = fs::OpenOptions::new().write(true).open(&one).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(5)).unwrap();
+        let (frontmatters, reread) = project_frontmatter_counted(&project_path).unwrap();
+        assert_eq!(reread, 1);
+        assert!(frontmatters.iter().any(|f| f.get("status").and_then(|v| v.as_str()) == Some("final")));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+}
@@ -1,9 +1,19 @@
 // Business logic and service layer
 // Handles file operations, database queries, search indexing, etc.
 
+pub mod activity_service;
+pub mod archive_service;
+pub mod character_service;
 pub mod file_service;
 pub mod project_service;
 pub mod document_service;
+pub mod duplicate_service;
+pub mod export_service;
+pub mod link_service;
+pub mod meta_cache_service;
+pub mod prose_service;
+pub mod settings_service;
+pub mod stats_service;
 
 // Future service modules will be added here:
 // pub mod db_service;
@@ -1,17 +1,37 @@
 use anyhow::{Context, Result};
 use serde_json;
+use std::collections::HashMap;
+use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
 use chrono::Utc;
 
-use crate::models::Project;
+use crate::models::{MergeReport, Project, ProjectConfig};
+use super::activity_service::rebase_activity;
+use super::document_service::{collect_document_paths, count_words, set_frontmatter_value, strip_frontmatter};
 use super::file_service::{ensure_dir, write_file, read_file};
+use super::link_service::{build_id_map, rewrite_wikilink_targets};
+use super::settings_service::{config_path, replace_recent_project_in};
+use super::stats_service::record_document_words;
 
 /// Default AYCD projects root directory
 pub fn get_projects_root() -> Result<PathBuf> {
     let home = dirs::home_dir()
         .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
 
-    Ok(home.join("AYCD").join("projects"))
+    Ok(projects_root_in(&home))
+}
+
+/// AYCD projects root directory under a home directory
+pub fn projects_root_in(home: &Path) -> PathBuf {
+    home.join("AYCD").join("projects")
+}
+
+/// Finds the project folder containing a path by looking for project.json
+pub fn find_project_root(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|dir| dir.join("project.json").is_file())
+        .map(Path::to_path_buf)
 }
 
 /// Initialize the standard AYCD project folder structure
@@ -49,31 +69,51 @@ pub fn create_project(name: &str, custom_path: Option<PathBuf>) -> Result<Projec
         get_projects_root()?.join(name)
     };
 
+    create_project_at(&project_path, name, write_project_metadata)
+}
+
+/// Creates a project at an exact path, removing everything it created if any
+/// step fails so no half-initialized project is left behind
+fn create_project_at<F>(project_path: &Path, name: &str, write_metadata: F) -> Result<Project>
+where
+    F: FnOnce(&Project) -> Result<()>,
+{
     // Check if project already exists
     if project_path.exists() {
         anyhow::bail!("Project already exists at: {}", project_path.display());
     }
 
-    // Initialize folder structure
-    init_project_structure(&project_path)?;
+    let result = init_project_structure(project_path).and_then(|_| {
+        // Create project metadata
+        let now = Utc::now().timestamp();
+        let project = Project {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            path: project_path.to_string_lossy().to_string(),
+            created_at: now,
+            modified_at: now,
+            settings: ProjectConfig::default(),
+        };
+
+        write_metadata(&project)?;
+        Ok(project)
+    });
+
+    if result.is_err() {
+        if let Err(e) = fs::remove_dir_all(project_path) {
+            eprintln!("Failed to clean up project folder {}: {}", project_path.display(), e);
+        }
+    }
 
-    // Create project metadata
-    let now = Utc::now().timestamp();
-    let project = Project {
-        id: uuid::Uuid::new_v4().to_string(),
-        name: name.to_string(),
-        path: project_path.to_string_lossy().to_string(),
-        created_at: now,
-        modified_at: now,
-    };
+    result
+}
 
-    // Write project.json
-    let project_json_path = project_path.join("project.json");
-    let project_json = serde_json::to_string_pretty(&project)
+/// Writes a project's project.json
+fn write_project_metadata(project: &Project) -> Result<()> {
+    let project_json_path = PathBuf::from(&project.path).join("project.json");
+    let project_json = serde_json::to_string_pretty(project)
         .context("Failed to serialize project metadata")?;
-    write_file(&project_json_path, &project_json)?;
-
-    Ok(project)
+    write_file(&project_json_path, &project_json)
 }
 
 /// Opens an existing project by reading its metadata
@@ -93,15 +133,18 @@ pub fn open_project(project_path: &Path) -> Result<Project> {
 
 /// Lists all projects in the default projects directory
 pub fn list_projects() -> Result<Vec<Project>> {
-    let projects_root = get_projects_root()?;
+    list_projects_in(&get_projects_root()?)
+}
 
+/// Lists all projects found directly inside a directory
+pub fn list_projects_in(projects_root: &Path) -> Result<Vec<Project>> {
     if !projects_root.exists() {
         return Ok(Vec::new());
     }
 
     let mut projects = Vec::new();
 
-    for entry in std::fs::read_dir(&projects_root)? {
+    for entry in fs::read_dir(projects_root)? {
         let entry = entry?;
         let path = entry.path();
 
@@ -114,28 +157,243 @@ pub fn list_projects() -> Result<Vec<Project>> {
     }
 
     // Sort by most recently modified
-    projects.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+    projects.sort_by_key(|p| std::cmp::Reverse(p.modified_at));
 
     Ok(projects)
 }
 
+/// Rebuilds project.json for a folder whose metadata is missing or corrupted.
+/// A valid existing project.json is left untouched and returned as-is.
+pub fn recover_project(project_path: &Path, name: &str) -> Result<Project> {
+    if !project_path.is_dir() {
+        anyhow::bail!("Project folder not found: {}", project_path.display());
+    }
+
+    if let Ok(project) = open_project(project_path) {
+        return Ok(project);
+    }
+
+    // Restore any missing standard folders
+    init_project_structure(project_path)?;
+
+    let metadata = fs::metadata(project_path)
+        .with_context(|| format!("Failed to read folder metadata: {:?}", project_path))?;
+    let modified_at = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_else(|| Utc::now().timestamp());
+    let created_at = metadata
+        .created()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(modified_at);
+
+    let project = Project {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: name.to_string(),
+        path: project_path.to_string_lossy().to_string(),
+        created_at,
+        modified_at,
+        settings: ProjectConfig::default(),
+    };
+
+    update_project(&project)?;
+
+    Ok(project)
+}
+
 /// Updates project metadata
 pub fn update_project(project: &Project) -> Result<()> {
-    let project_path = PathBuf::from(&project.path);
-    let project_json_path = project_path.join("project.json");
+    write_project_metadata(project)
+}
 
-    let project_json = serde_json::to_string_pretty(project)
-        .context("Failed to serialize project metadata")?;
-    write_file(&project_json_path, &project_json)?;
+/// Renames a project: updates its display name and moves its folder to the
+/// matching folder name, adding " (2)", " (3)", ... if another folder
+/// already uses it. Recent-projects entries follow the folder.
+pub fn rename_project(project_path: &Path, new_name: &str) -> Result<Project> {
+    rename_project_with(project_path, new_name, &config_path()?)
+}
 
-    Ok(())
+/// Renames a project, updating the recent-projects list in the given config
+fn rename_project_with(project_path: &Path, new_name: &str, config_path: &Path) -> Result<Project> {
+    let new_name = new_name.trim();
+    if new_name.is_empty() {
+        anyhow::bail!("Project name cannot be empty");
+    }
+
+    let mut project = open_project(project_path)?;
+    let parent = project_path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("Project folder has no parent: {}", project_path.display()))?;
+
+    let mut target = parent.join(project_folder_name(new_name));
+    if target != project_path && !is_same_folder(&target, project_path) {
+        target = unique_folder_path(&target);
+        fs::rename(project_path, &target)
+            .with_context(|| format!("Failed to move project folder to {:?}", target))?;
+    } else {
+        target = project_path.to_path_buf();
+    }
+
+    project.name = new_name.to_string();
+    project.path = target.to_string_lossy().to_string();
+    project.modified_at = Utc::now().timestamp();
+    update_project(&project)?;
+
+    if target != project_path {
+        // Metadata cache entries are keyed by the old absolute paths; the
+        // stats cache is keyed by project-relative paths and stays valid
+        let _ = fs::remove_file(target.join("cache").join("meta.json"));
+        if let Err(e) = rebase_activity(&target, project_path) {
+            eprintln!("Failed to update activity log for {}: {}", target.display(), e);
+        }
+
+        if let Err(e) = replace_recent_project_in(config_path, project_path, &target) {
+            eprintln!("Failed to update recent projects for {}: {}", target.display(), e);
+        }
+    }
+
+    Ok(project)
+}
+
+/// Folder name for a project name, with path separators and characters
+/// unsupported on common filesystems replaced
+fn project_folder_name(name: &str) -> String {
+    let folder: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c if c.is_control() => '-',
+            c => c,
+        })
+        .collect();
+    let folder = folder.trim().trim_matches('.').trim();
+
+    if folder.is_empty() {
+        "Untitled".to_string()
+    } else {
+        folder.to_string()
+    }
+}
+
+/// True when both paths exist and are the same folder (e.g. a case-only
+/// rename on a case-insensitive filesystem)
+fn is_same_folder(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Appends " (2)", " (3)", ... to a folder name until it doesn't collide
+fn unique_folder_path(path: &Path) -> PathBuf {
+    if !path.exists() {
+        return path.to_path_buf();
+    }
+
+    let name = path.file_name().and_then(|s| s.to_str()).unwrap_or("Untitled");
+    (2..)
+        .map(|n| path.with_file_name(format!("{} ({})", name, n)))
+        .find(|candidate| !candidate.exists())
+        .expect("unbounded range always yields a free name")
+}
+
+/// Copies all documents of one project into another, optionally nesting them
+/// under a prefix folder inside each category. Every copied document gets a
+/// fresh id, and wikilinks resolving to a source document (by id, title or
+/// file name) are rewritten to that document's new id.
+pub fn merge_project(
+    source_path: &Path,
+    dest_path: &Path,
+    category_prefix: Option<&str>,
+) -> Result<MergeReport> {
+    // Both sides must be valid projects
+    open_project(source_path)?;
+    open_project(dest_path)?;
+    if is_same_folder(source_path, dest_path) {
+        anyhow::bail!("Cannot merge a project into itself: {}", source_path.display());
+    }
+
+    let source_paths = collect_document_paths(source_path)?;
+
+    // Assign new ids up front so links can be rewritten in a single pass
+    let id_map = build_id_map(source_path)?;
+    let new_ids: HashMap<PathBuf, String> = id_map
+        .entries()
+        .iter()
+        .map(|entry| (entry.path.clone(), uuid::Uuid::new_v4().to_string()))
+        .collect();
+    let prefix = category_prefix.map(str::trim).filter(|p| !p.is_empty());
+
+    let mut report = MergeReport {
+        documents_merged: 0,
+        links_rewritten: 0,
+    };
+
+    for path in source_paths {
+        // WORLD/Cast/Anna.md -> WORLD/Cast/<prefix>/Anna.md
+        let relative = path.strip_prefix(source_path)?;
+        let mut components = relative.components();
+        let mut target = dest_path.join(components.next().context("Invalid document path")?);
+        if components.clone().count() > 1 {
+            target.push(components.next().context("Invalid document path")?);
+        }
+        if let Some(prefix) = prefix {
+            target.push(prefix);
+        }
+        target.push(components.as_path());
+        let target = unique_path(&target);
+
+        let new_id = new_ids
+            .get(&path)
+            .cloned()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let content = read_file(&path)?;
+        let content = set_frontmatter_value(&content, "id", &new_id);
+        let (content, rewritten) = rewrite_wikilink_targets(&content, |target| {
+            id_map.resolve(target).and_then(|entry| new_ids.get(&entry.path).cloned())
+        });
+
+        if let Some(parent) = target.parent() {
+            ensure_dir(parent)?;
+        }
+        write_file(&target, &content)?;
+        record_document_words(&target, None, Some(count_words(strip_frontmatter(&content))));
+
+        report.documents_merged += 1;
+        report.links_rewritten += rewritten;
+    }
+
+    Ok(report)
+}
+
+/// Appends " (2)", " (3)", ... to a file name until it doesn't collide
+fn unique_path(path: &Path) -> PathBuf {
+    if !path.exists() {
+        return path.to_path_buf();
+    }
+
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("Untitled");
+    let extension = path.extension().and_then(|s| s.to_str());
+    (2..)
+        .map(|n| {
+            let name = match extension {
+                Some(ext) => format!("{} ({}).{}", stem, n, ext),
+                None => format!("{} ({})", stem, n),
+            };
+            path.with_file_name(name)
+        })
+        .find(|candidate| !candidate.exists())
+        .expect("unbounded range always yields a free name")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::env;
-    use std::fs;
 
     #[test]
     fn test_create_and_open_project() {
@@ -159,4 +417,207 @@ mod tests {
 
         fs::remove_dir_all(&temp_dir).unwrap();
     }
+
+    #[test]
+    fn test_recover_project() {
+        let temp_dir = env::temp_dir().join("aycd_test_projects_recover");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let project = create_project("lost-novel", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+
+        // Missing project.json locks the project out
+        fs::remove_file(project_path.join("project.json")).unwrap();
+        assert!(open_project(&project_path).is_err());
+        assert!(list_projects_in(&temp_dir).unwrap().is_empty());
+
+        let recovered = recover_project(&project_path, "lost-novel").unwrap();
+        assert_ne!(recovered.id, project.id);
+        assert_eq!(open_project(&project_path).unwrap().id, recovered.id);
+
+        let listed = list_projects_in(&temp_dir).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].name, "lost-novel");
+
+        // A valid project.json is never overwritten
+        let again = recover_project(&project_path, "other-name").unwrap();
+        assert_eq!(again.id, recovered.id);
+        assert_eq!(again.name, "lost-novel");
+
+        // Corrupted metadata is replaced
+        fs::write(project_path.join("project.json"), "{ not json").unwrap();
+        assert!(recover_project(&project_path, "lost-novel").is_ok());
+        assert!(open_project(&project_path).is_ok());
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_merge_project() {
+        let temp_dir = env::temp_dir().join("aycd_test_projects_merge");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let source = create_project("book-one", Some(temp_dir.clone())).unwrap();
+        let dest = create_project("series", Some(temp_dir.clone())).unwrap();
+        let source_path = PathBuf::from(&source.path);
+        let dest_path = PathBuf::from(&dest.path);
+
+        fs::write(source_path.join("WORLD/Cast/Anna.md"), "---\nid: anna\ntitle: Anna\n---\n\nHero").unwrap();
+        fs::write(
+            source_path.join("NARRATIVE/Drafts/One.md"),
+            "---\nid: one\ntitle: One\n---\n\n[[anna|Anna]] arrives.",
+        )
+        .unwrap();
+        fs::write(dest_path.join("WORLD/Cast/Anna.md"), "---\nid: anna\ntitle: Anna\n---\n\nOther Anna").unwrap();
+        fs::write(dest_path.join("NARRATIVE/Drafts/Intro.md"), "---\nid: intro\n---\n\nIntro").unwrap();
+
+        let report = merge_project(&source_path, &dest_path, Some("Book One")).unwrap();
+        assert_eq!(report.documents_merged, 2);
+        assert_eq!(report.links_rewritten, 1);
+
+        let documents = crate::services::document_service::list_all_documents(&dest_path).unwrap();
+        assert_eq!(documents.len(), 4);
+        let ids: std::collections::HashSet<_> = documents.iter().map(|d| d.id.clone()).collect();
+        assert_eq!(ids.len(), 4);
+
+        // The rewritten link points at the merged copy of Anna
+        let merged_anna = documents
+            .iter()
+            .find(|d| d.path.ends_with("Cast/Book One/Anna.md"))
+            .unwrap();
+        let merged_one_path = dest_path.join("NARRATIVE/Drafts/Book One/One.md");
+        let merged_one = fs::read_to_string(&merged_one_path).unwrap();
+        assert!(merged_one.contains(&format!("[[{}|Anna]]", merged_anna.id)));
+
+        // Prefixed documents stay inside their category, so they still compile
+        let manuscript = crate::services::export_service::manuscript_paths(&dest_path).unwrap();
+        assert!(manuscript.contains(&merged_one_path));
+
+        // Merging a project into itself is rejected
+        assert!(merge_project(&dest_path, &dest_path, None).is_err());
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_merge_project_rewrites_title_links() {
+        let temp_dir = env::temp_dir().join("aycd_test_projects_merge_titles");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let source = create_project("book-two", Some(temp_dir.clone())).unwrap();
+        let dest = create_project("series", Some(temp_dir.clone())).unwrap();
+        let source_path = PathBuf::from(&source.path);
+        let dest_path = PathBuf::from(&dest.path);
+
+        // Both projects have a document titled "Anna"
+        fs::write(source_path.join("WORLD/Cast/Anna.md"), "---\ntitle: Anna\n---\n\nSource Anna").unwrap();
+        fs::write(source_path.join("NARRATIVE/Drafts/Two.md"), "---\ntitle: Two\n---\n\n[[Anna]] returns.").unwrap();
+        fs::write(dest_path.join("WORLD/Cast/Anna.md"), "---\nid: dest-anna\ntitle: Anna\n---\n\nDest Anna").unwrap();
+
+        let report = merge_project(&source_path, &dest_path, None).unwrap();
+        assert_eq!(report.links_rewritten, 1);
+
+        let merged_anna = crate::services::document_service::read_document(&dest_path.join("WORLD/Cast/Anna (2).md")).unwrap();
+        assert!(merged_anna.content.contains("Source Anna"));
+        let merged_two = fs::read_to_string(dest_path.join("NARRATIVE/Drafts/Two.md")).unwrap();
+        assert!(merged_two.contains(&format!("[[{}]] returns.", merged_anna.id)));
+        assert!(!merged_two.contains("dest-anna"));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_rename_project_uniquifies_colliding_folder() {
+        let temp_dir = env::temp_dir().join("aycd_test_projects_rename");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let config = temp_dir.join("config.json");
+
+        let existing = create_project("Night-Falls", Some(temp_dir.clone())).unwrap();
+        let draft = create_project("draft", Some(temp_dir.clone())).unwrap();
+        let draft_path = PathBuf::from(&draft.path);
+        crate::services::settings_service::record_recent_project_in(&config, Path::new(&existing.path)).unwrap();
+        crate::services::settings_service::record_recent_project_in(&config, &draft_path).unwrap();
+
+        // "Night/Falls" maps to the same folder as the existing project
+        let renamed = rename_project_with(&draft_path, "Night/Falls", &config).unwrap();
+        assert_eq!(renamed.name, "Night/Falls");
+        assert_eq!(renamed.id, draft.id);
+        let renamed_path = PathBuf::from(&renamed.path);
+        assert_eq!(renamed_path, temp_dir.join("Night-Falls (2)"));
+        assert!(!draft_path.exists());
+        assert_eq!(open_project(&renamed_path).unwrap().name, "Night/Falls");
+
+        // The existing project is untouched
+        let existing_path = PathBuf::from(&existing.path);
+        assert_eq!(open_project(&existing_path).unwrap().id, existing.id);
+
+        // The recents entry follows the folder and keeps its position
+        let recents = crate::services::settings_service::load_app_config_from(&config)
+            .unwrap()
+            .recent_projects;
+        assert_eq!(recents, vec![renamed.path.clone(), existing.path.clone()]);
+
+        // Renaming to the current folder name only changes the display name
+        let same = rename_project_with(&renamed_path, "Night-Falls (2)", &config).unwrap();
+        assert_eq!(PathBuf::from(&same.path), renamed_path);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_rename_project_rebases_activity_and_keeps_stats() {
+        use crate::models::{ActivityKind, DocumentType};
+        use crate::services::activity_service::project_activity;
+        use crate::services::document_service::{create_document, move_document, update_document};
+        use crate::services::stats_service::{project_stats, recompute_stats};
+
+        let temp_dir = env::temp_dir().join("aycd_test_projects_rename_caches");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let config = temp_dir.join("config.json");
+
+        let project = create_project("draft", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+        let doc = create_document(&project_path, "One", DocumentType::Narrative, "Drafts", None).unwrap();
+        project_stats(&project_path).unwrap();
+        move_document(&project_path, Path::new(&doc.path), "Final", None).unwrap();
+
+        let renamed = rename_project_with(&project_path, "Novel", &config).unwrap();
+        let renamed_path = PathBuf::from(&renamed.path);
+
+        // Logged paths follow the folder, including a move's previous location
+        let activity = project_activity(&renamed_path, 10).unwrap();
+        assert!(!activity.is_empty());
+        assert!(activity.iter().all(|a| Path::new(&a.path).starts_with(&renamed_path)));
+        let moved = activity.iter().find(|a| a.kind == ActivityKind::Move).unwrap();
+        assert_eq!(
+            PathBuf::from(moved.previous_path.as_ref().unwrap()),
+            renamed_path.join("NARRATIVE/Drafts/One.md")
+        );
+
+        // Saves after the rename still update the cached stats correctly
+        update_document(&renamed_path.join("NARRATIVE/Final/One.md"), "three more words").unwrap();
+        assert_eq!(project_stats(&renamed_path).unwrap(), recompute_stats(&renamed_path).unwrap());
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_create_project_rolls_back_on_failure() {
+        let temp_dir = env::temp_dir().join("aycd_test_projects_rollback");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let project_path = temp_dir.join("broken-novel");
+
+        let result = create_project_at(&project_path, "broken-novel", |_| {
+            anyhow::bail!("Simulated write failure for project.json")
+        });
+
+        assert!(result.is_err());
+        assert!(!project_path.exists());
+        assert!(list_projects_in(&temp_dir).unwrap().is_empty());
+
+        // The name is free again once the failure is resolved
+        assert!(create_project("broken-novel", Some(temp_dir.clone())).is_ok());
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
 }
@@ -0,0 +1,226 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::models::{ProseWarning, ProseWarningKind};
+use super::document_service::{read_document, strip_frontmatter};
+
+/// Default word limit before a sentence is flagged
+pub const DEFAULT_SENTENCE_WORD_LIMIT: usize = 40;
+
+/// Default word limit before a paragraph is flagged
+pub const DEFAULT_PARAGRAPH_WORD_LIMIT: usize = 200;
+
+/// Default scene-break lines
+pub const DEFAULT_SCENE_BREAK_MARKERS: [&str; 3] = ["* * *", "***", "---"];
+
+/// Returns true for lines opening or closing a fenced code block
+fn is_code_fence(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+/// Returns true when a word ends a sentence, ignoring closing quotes and brackets
+fn ends_sentence(word: &str) -> bool {
+    word.trim_end_matches(['"', '\'', ')', ']', '”', '’', '*', '_'])
+        .ends_with(['.', '!', '?', '…'])
+}
+
+/// Flags sentences and paragraphs longer than the given word limits.
+/// Fenced code blocks and blockquotes are skipped. Line numbers are 1-based
+/// and relative to `body`.
+pub fn prose_warnings(body: &str, sentence_word_limit: usize, paragraph_word_limit: usize) -> Vec<ProseWarning> {
+    let mut warnings = Vec::new();
+    let mut paragraph: Vec<(usize, &str)> = Vec::new();
+    let mut in_code = false;
+
+    for (index, line) in body.lines().enumerate() {
+        if is_code_fence(line) {
+            in_code = !in_code;
+            check_paragraph(&paragraph, sentence_word_limit, paragraph_word_limit, &mut warnings);
+            paragraph.clear();
+            continue;
+        }
+
+        // Blank lines, code and quotes all end the current paragraph
+        if in_code || line.trim().is_empty() || line.trim_start().starts_with('>') {
+            check_paragraph(&paragraph, sentence_word_limit, paragraph_word_limit, &mut warnings);
+            paragraph.clear();
+            continue;
+        }
+
+        paragraph.push((index + 1, line));
+    }
+    check_paragraph(&paragraph, sentence_word_limit, paragraph_word_limit, &mut warnings);
+
+    warnings
+}
+
+/// Checks one paragraph (and the sentences inside it) against the limits
+fn check_paragraph(
+    lines: &[(usize, &str)],
+    sentence_word_limit: usize,
+    paragraph_word_limit: usize,
+    warnings: &mut Vec<ProseWarning>,
+) {
+    let (Some(&(first_line, _)), Some(&(last_line, _))) = (lines.first(), lines.last()) else {
+        return;
+    };
+
+    let mut paragraph_words = 0;
+    let mut sentence_words = 0;
+    let mut sentence_start = first_line;
+
+    for &(line_number, line) in lines {
+        for word in line.split_whitespace() {
+            if sentence_words == 0 {
+                sentence_start = line_number;
+            }
+            sentence_words += 1;
+            paragraph_words += 1;
+
+            if ends_sentence(word) {
+                if sentence_words > sentence_word_limit {
+                    warnings.push(ProseWarning {
+                        kind: ProseWarningKind::Sentence,
+                        start_line: sentence_start,
+                        end_line: line_number,
+                        word_count: sentence_words,
+                    });
+                }
+                sentence_words = 0;
+            }
+        }
+    }
+
+    // A trailing sentence without final punctuation
+    if sentence_words > sentence_word_limit {
+        warnings.push(ProseWarning {
+            kind: ProseWarningKind::Sentence,
+            start_line: sentence_start,
+            end_line: last_line,
+            word_count: sentence_words,
+        });
+    }
+
+    if paragraph_words > paragraph_word_limit {
+        warnings.push(ProseWarning {
+            kind: ProseWarningKind::Paragraph,
+            start_line: first_line,
+            end_line: last_line,
+            word_count: paragraph_words,
+        });
+    }
+}
+
+/// Computes prose warnings for a document, with line numbers relative to the
+/// whole file (frontmatter included) so the editor can highlight them
+pub fn document_prose_warnings(
+    document_path: &Path,
+    sentence_word_limit: usize,
+    paragraph_word_limit: usize,
+) -> Result<Vec<ProseWarning>> {
+    let document = read_document(document_path)?;
+    let body = strip_frontmatter(&document.content);
+    let line_offset = document.content[..document.content.len() - body.len()]
+        .matches('\n')
+        .count();
+
+    let mut warnings = prose_warnings(body, sentence_word_limit, paragraph_word_limit);
+    for warning in &mut warnings {
+        warning.start_line += line_offset;
+        warning.end_line += line_offset;
+    }
+
+    Ok(warnings)
+}
+
+/// Counts the scenes in a body, split on lines consisting only of one of the
+/// break markers. Markers inside fenced code blocks don't count, and empty
+/// scenes (e.g. a break at the very start or end) are ignored.
+pub fn count_scenes(body: &str, break_markers: &[&str]) -> usize {
+    let mut scenes = 0;
+    let mut scene_has_content = false;
+    let mut in_code = false;
+
+    for line in body.lines() {
+        if is_code_fence(line) {
+            in_code = !in_code;
+            scene_has_content = true;
+            continue;
+        }
+
+        let trimmed = line.trim();
+        if !in_code && break_markers.contains(&trimmed) {
+            if scene_has_content {
+                scenes += 1;
+            }
+            scene_has_content = false;
+            continue;
+        }
+
+        if !trimmed.is_empty() {
+            scene_has_content = true;
+        }
+    }
+
+    if scene_has_content {
+        scenes += 1;
+    }
+
+    scenes
+}
+
+/// Counts the scenes in a document body (frontmatter excluded)
+pub fn document_scene_count(document_path: &Path, break_markers: &[&str]) -> Result<usize> {
+    let document = read_document(document_path)?;
+    Ok(count_scenes(strip_frontmatter(&document.content), break_markers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prose_warnings_flags_long_sentence() {
+        let long_sentence = ["word"; 45].join(" ");
+        let body = format!("A short sentence.\n\n{}.\nAnother short one.", long_sentence);
+
+        let warnings = prose_warnings(&body, DEFAULT_SENTENCE_WORD_LIMIT, DEFAULT_PARAGRAPH_WORD_LIMIT);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, ProseWarningKind::Sentence);
+        assert_eq!(warnings[0].start_line, 3);
+        assert_eq!(warnings[0].end_line, 3);
+        assert_eq!(warnings[0].word_count, 45);
+    }
+
+    #[test]
+    fn test_prose_warnings_skips_code_and_quotes() {
+        let long = ["word"; 20].join(" ");
+        let body = format!("```\n{0}\n```\n\n> {0}\n\nFine here.", long);
+
+        assert!(prose_warnings(&body, 10, 15).is_empty());
+    }
+
+    #[test]
+    fn test_prose_warnings_flags_long_paragraph() {
+        let sentence = ["word"; 9].join(" ") + ".";
+        let body = format!("{0}\n{0}\n{0}", sentence);
+
+        let warnings = prose_warnings(&body, 10, 25);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, ProseWarningKind::Paragraph);
+        assert_eq!((warnings[0].start_line, warnings[0].end_line), (1, 3));
+    }
+
+    #[test]
+    fn test_count_scenes() {
+        let body = "Opening scene.\n\n* * *\n\nSecond scene.\n\n* * *\n\nThird scene.";
+        assert_eq!(count_scenes(body, &DEFAULT_SCENE_BREAK_MARKERS), 3);
+
+        // Breaks in code, leading breaks and unknown markers don't add scenes
+        let body = "---\n\nOnly scene.\n\n```\n* * *\n```\n\n~ ~ ~\n\nStill the same.";
+        assert_eq!(count_scenes(body, &DEFAULT_SCENE_BREAK_MARKERS), 1);
+        assert_eq!(count_scenes(body, &["~ ~ ~"]), 2);
+        assert_eq!(count_scenes("", &DEFAULT_SCENE_BREAK_MARKERS), 0);
+    }
+}
@@ -0,0 +1,239 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use crate::models::{AppConfig, PathInfo, PathStatus};
+use super::file_service::{ensure_dir, is_writable, read_file, write_file};
+use super::project_service::{open_project, projects_root_in};
+
+/// Path of the app-wide config file (~/AYCD/config.json)
+pub fn config_path() -> Result<PathBuf> {
+    let home = dirs::home_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
+
+    Ok(config_path_in(&home))
+}
+
+/// Path of the app-wide config file under a home directory
+fn config_path_in(home: &Path) -> PathBuf {
+    home.join("AYCD").join("config.json")
+}
+
+/// Loads the app config from a file, using defaults when it doesn't exist
+pub fn load_app_config_from(path: &Path) -> Result<AppConfig> {
+    if !path.exists() {
+        return Ok(AppConfig::default());
+    }
+
+    let content = read_file(path)?;
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse config: {:?}", path))
+}
+
+/// Saves the app config to a file
+pub fn save_app_config_to(path: &Path, config: &AppConfig) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        ensure_dir(parent)?;
+    }
+
+    let json = serde_json::to_string_pretty(config).context("Failed to serialize config")?;
+    write_file(path, &json)
+}
+
+/// Loads the app-wide config
+pub fn load_app_config() -> Result<AppConfig> {
+    load_app_config_from(&config_path()?)
+}
+
+/// Saves the app-wide config
+pub fn save_app_config(config: &AppConfig) -> Result<()> {
+    save_app_config_to(&config_path()?, config)
+}
+
+/// Number of projects kept in the recent-projects list
+const MAX_RECENT_PROJECTS: usize = 10;
+
+/// Moves a project to the front of the recent-projects list in a config file.
+/// The file is only rewritten when the list changes.
+pub fn record_recent_project_in(config_path: &Path, project_path: &Path) -> Result<()> {
+    let mut config = load_app_config_from(config_path)?;
+    let project_path = project_path.to_string_lossy().to_string();
+
+    let mut recent_projects = config.recent_projects.clone();
+    recent_projects.retain(|p| *p != project_path);
+    recent_projects.insert(0, project_path);
+    recent_projects.truncate(MAX_RECENT_PROJECTS);
+    if recent_projects == config.recent_projects {
+        return Ok(());
+    }
+
+    config.recent_projects = recent_projects;
+    save_app_config_to(config_path, &config)
+}
+
+/// Moves a project to the front of the app-wide recent-projects list
+pub fn record_recent_project(project_path: &Path) -> Result<()> {
+    record_recent_project_in(&config_path()?, project_path)
+}
+
+/// Points recent-projects entries for a moved project at its new folder,
+/// keeping their position in the list
+pub fn replace_recent_project_in(config_path: &Path, old_path: &Path, new_path: &Path) -> Result<()> {
+    let mut config = load_app_config_from(config_path)?;
+    let old_path = old_path.to_string_lossy();
+    let new_path = new_path.to_string_lossy().to_string();
+
+    if !config.recent_projects.iter().any(|p| *p == old_path) {
+        return Ok(());
+    }
+
+    let mut seen = false;
+    config.recent_projects = config
+        .recent_projects
+        .into_iter()
+        .map(|p| if p == old_path { new_path.clone() } else { p })
+        .filter(|p| *p != new_path || !std::mem::replace(&mut seen, true))
+        .collect();
+
+    save_app_config_to(config_path, &config)
+}
+
+/// Resolves the author for a project: the project's own setting, falling
+/// back to the default in a config file. Returns `None` when neither is set.
+pub fn resolve_author_in(config_path: &Path, project_path: &Path) -> Option<String> {
+    let project_author = open_project(project_path)
+        .ok()
+        .and_then(|project| project.settings.author);
+    let author = project_author
+        .or_else(|| load_app_config_from(config_path).ok().and_then(|config| config.author))?;
+
+    let author = author.trim();
+    (!author.is_empty()).then(|| author.to_string())
+}
+
+/// Describes a path for the diagnostics screen
+fn path_status(path: &Path) -> PathStatus {
+    PathStatus {
+        path: path.to_string_lossy().to_string(),
+        exists: path.exists(),
+        writable: is_writable(path),
+    }
+}
+
+/// Reports the resolved home, projects root and config locations, and whether
+/// each exists and is writable
+pub fn get_paths() -> PathInfo {
+    get_paths_in(dirs::home_dir().as_deref())
+}
+
+/// Reports the locations derived from a home directory. Without one, none of
+/// them can be resolved.
+fn get_paths_in(home: Option<&Path>) -> PathInfo {
+    PathInfo {
+        home_dir: home.map(path_status),
+        projects_root: home.map(|home| path_status(&projects_root_in(home))),
+        config_path: home.map(|home| path_status(&config_path_in(home))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn test_app_config_round_trip() {
+        let temp_dir = env::temp_dir().join("aycd_settings_test_config");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let path = temp_dir.join("config.json");
+
+        assert!(load_app_config_from(&path).unwrap().author.is_none());
+
+        fs::create_dir_all(&temp_dir).unwrap();
+        fs::write(&path, r#"{"author": "Jane Writer", "editor": {"fontSize": 16}}"#).unwrap();
+        let config = load_app_config_from(&path).unwrap();
+        assert_eq!(config.author.as_deref(), Some("Jane Writer"));
+
+        // Unknown preferences survive a save
+        save_app_config_to(&path, &config).unwrap();
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("fontSize"));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_record_recent_project_writes_only_on_change() {
+        let temp_dir = env::temp_dir().join("aycd_settings_test_recents");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let path = temp_dir.join("config.json");
+
+        record_recent_project_in(&path, Path::new("/projects/a")).unwrap();
+        record_recent_project_in(&path, Path::new("/projects/b")).unwrap();
+        assert_eq!(load_app_config_from(&path).unwrap().recent_projects, ["/projects/b", "/projects/a"]);
+
+        // Reopening the most recent project leaves the file untouched
+        let compact = r#"{"recentProjects":["/projects/b","/projects/a"]}"#;
+        fs::write(&path, compact).unwrap();
+        record_recent_project_in(&path, Path::new("/projects/b")).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), compact);
+
+        record_recent_project_in(&path, Path::new("/projects/a")).unwrap();
+        assert_eq!(load_app_config_from(&path).unwrap().recent_projects, ["/projects/a", "/projects/b"]);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_author_falls_back_to_app_config() {
+        let temp_dir = env::temp_dir().join("aycd_settings_test_author");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let config = temp_dir.join("config.json");
+
+        let mut project = crate::services::project_service::create_project("novel", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+        assert_eq!(resolve_author_in(&config, &project_path), None);
+
+        // Blank app-wide authors count as unset
+        fs::write(&config, r#"{"author": "  "}"#).unwrap();
+        assert_eq!(resolve_author_in(&config, &project_path), None);
+
+        fs::write(&config, r#"{"author": " Jane Writer "}"#).unwrap();
+        assert_eq!(resolve_author_in(&config, &project_path).as_deref(), Some("Jane Writer"));
+
+        // The project's own author wins over the app-wide default
+        project.settings.author = Some("Pen Name".to_string());
+        crate::services::project_service::update_project(&project).unwrap();
+        assert_eq!(resolve_author_in(&config, &project_path).as_deref(), Some("Pen Name"));
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_get_paths_matches_projects_root() {
+        let home = env::temp_dir().join("aycd_settings_test_paths");
+        let _ = fs::remove_dir_all(&home);
+        fs::create_dir_all(&home).unwrap();
+        let projects_root = projects_root_in(&home);
+
+        // Missing folders are reported as creatable
+        let info = get_paths_in(Some(&home));
+        assert!(info.home_dir.unwrap().writable);
+        let reported = info.projects_root.unwrap();
+        assert_eq!(reported.path, projects_root.to_string_lossy());
+        assert!(!reported.exists);
+        assert!(reported.writable);
+        let config = info.config_path.unwrap();
+        assert_eq!(config.path, home.join("AYCD/config.json").to_string_lossy());
+        assert!(!config.exists);
+
+        fs::create_dir_all(&projects_root).unwrap();
+        let reported = get_paths_in(Some(&home)).projects_root.unwrap();
+        assert!(reported.exists);
+        assert!(reported.writable);
+
+        let unresolved = get_paths_in(None);
+        assert!(unresolved.home_dir.is_none() && unresolved.projects_root.is_none() && unresolved.config_path.is_none());
+
+        fs::remove_dir_all(&home).unwrap();
+    }
+}
@@ -0,0 +1,382 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::models::{GoalProgress, Project, ProjectStats};
+use super::document_service::{collect_document_paths, count_words, read_document, strip_frontmatter};
+use super::file_service::{ensure_dir, read_file, with_dir_lock, write_file};
+use super::project_service::{find_project_root, open_project, update_project};
+
+/// Aggregated project stats plus the per-document word counts needed to
+/// apply deltas, stored in cache/stats.json
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StatsCache {
+    stats: ProjectStats,
+    /// Word counts keyed by project-relative path ("NARRATIVE/Drafts/One.md")
+    documents: HashMap<String, usize>,
+}
+
+impl StatsCache {
+    /// Adds a document's words to the totals, or removes them when `add` is false
+    fn apply(&mut self, key: &str, word_count: usize, add: bool) {
+        let stats = &mut self.stats;
+        let mode_words = if key.starts_with("WORLD/") {
+            Some(&mut stats.world_words)
+        } else if key.starts_with("NARRATIVE/") {
+            Some(&mut stats.narrative_words)
+        } else {
+            None
+        };
+
+        if add {
+            stats.total_words += word_count;
+            stats.document_count += 1;
+            if let Some(words) = mode_words {
+                *words += word_count;
+            }
+        } else {
+            stats.total_words = stats.total_words.saturating_sub(word_count);
+            stats.document_count = stats.document_count.saturating_sub(1);
+            if let Some(words) = mode_words {
+                *words = words.saturating_sub(word_count);
+            }
+        }
+    }
+
+    /// Sets a document's word count (`None` removes it), adjusting the totals
+    fn set(&mut self, key: String, word_count: Option<usize>) {
+        if let Some(old) = self.documents.remove(&key) {
+            self.apply(&key, old, false);
+        }
+        if let Some(word_count) = word_count {
+            self.apply(&key, word_count, true);
+            self.documents.insert(key, word_count);
+        }
+    }
+}
+
+/// Location of the stats cache inside a project
+fn stats_cache_path(project_path: &Path) -> PathBuf {
+    project_path.join("cache").join("stats.json")
+}
+
+/// Loads a project's stats cache, if there is a readable one
+fn load_stats_cache(project_path: &Path) -> Option<StatsCache> {
+    read_file(&stats_cache_path(project_path))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+/// Saves a project's stats cache
+fn save_stats_cache(project_path: &Path, cache: &StatsCache) -> Result<()> {
+    ensure_dir(&project_path.join("cache"))?;
+    let json = serde_json::to_string(cache).context("Failed to serialize stats cache")?;
+    write_file(&stats_cache_path(project_path), &json)
+}
+
+/// Cache key of a document: its project-relative path with `/` separators
+fn stats_key(project_path: &Path, document_path: &Path) -> Option<String> {
+    let relative = document_path.strip_prefix(project_path).ok()?;
+    Some(
+        relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+    )
+}
+
+/// Rebuilds the stats cache by reading every document in the project
+pub fn recompute_stats(project_path: &Path) -> Result<ProjectStats> {
+    // Held throughout so a save landing mid-walk isn't overwritten by older counts
+    with_dir_lock(project_path, || recompute_stats_locked(project_path)).map(|cache| cache.stats)
+}
+
+/// Rebuilds the stats cache; the caller holds the project lock
+fn recompute_stats_locked(project_path: &Path) -> Result<StatsCache> {
+    let mut cache = StatsCache::default();
+
+    for path in collect_document_paths(project_path)? {
+        let Some(key) = stats_key(project_path, &path) else {
+            continue;
+        };
+        match read_document(&path) {
+            Ok(doc) => cache.set(key, Some(doc.word_count)),
+            Err(e) => eprintln!("Failed to read document {}: {}", path.display(), e),
+        }
+    }
+
+    save_stats_cache(project_path, &cache)?;
+    Ok(cache)
+}
+
+/// Returns the cached project stats, rebuilding them when there is no cache yet
+pub fn project_stats(project_path: &Path) -> Result<ProjectStats> {
+    match load_stats_cache(project_path) {
+        Some(cache) => Ok(cache.stats),
+        None => recompute_stats(project_path),
+    }
+}
+
+/// Applies a document's new word count (`None` once deleted) to its project's
+/// cached stats. `previous_path` is the document's old location after a move.
+/// Projects without a stats cache are left alone; it's built on first read.
+pub fn record_document_words(document_path: &Path, previous_path: Option<&Path>, word_count: Option<usize>) {
+    let Some(project_path) = find_project_root(document_path) else {
+        return;
+    };
+
+    with_dir_lock(&project_path, || {
+        apply_document_words(&project_path, document_path, previous_path, word_count)
+    });
+}
+
+/// Applies a document's word count to the stats cache; the caller holds the
+/// project lock
+fn apply_document_words(
+    project_path: &Path,
+    document_path: &Path,
+    previous_path: Option<&Path>,
+    word_count: Option<usize>,
+) {
+    let Some(mut cache) = load_stats_cache(project_path) else {
+        return;
+    };
+
+    if let Some(key) = previous_path.and_then(|path| stats_key(project_path, path)) {
+        cache.set(key, None);
+    }
+    if let Some(key) = stats_key(project_path, document_path) {
+        cache.set(key, word_count);
+    }
+
+    if let Err(e) = save_stats_cache(project_path, &cache) {
+        eprintln!("Failed to update stats for {}: {}", document_path.display(), e);
+    }
+}
+
+/// Recounts a document from disk and applies the count to its project's
+/// cached stats. The read happens under the project lock, so it can't land
+/// after (and overwrite) a newer save's count. Returns `None` when the
+/// document can't be read.
+pub fn recount_document_words(document_path: &Path) -> Option<usize> {
+    let count = || {
+        read_file(document_path)
+            .ok()
+            .map(|content| count_words(strip_frontmatter(&content)))
+    };
+    let Some(project_path) = find_project_root(document_path) else {
+        return count();
+    };
+
+    with_dir_lock(&project_path, || {
+        let word_count = count()?;
+        apply_document_words(&project_path, document_path, None, Some(word_count));
+        Some(word_count)
+    })
+}
+
+/// Normalizes a category path like "NARRATIVE\Drafts/" to "NARRATIVE/Drafts"
+fn normalize_category(category: &str) -> String {
+    category
+        .split(['/', '\\'])
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Sums the cached word count of every document under a category folder,
+/// rebuilding the stats cache when there is none yet
+fn category_word_count(project_path: &Path, category: &str) -> Result<usize> {
+    let cache = match load_stats_cache(project_path) {
+        Some(cache) => cache,
+        None => with_dir_lock(project_path, || recompute_stats_locked(project_path))?,
+    };
+
+    let prefix = format!("{}/", category);
+    Ok(cache
+        .documents
+        .iter()
+        .filter(|(key, _)| key.starts_with(&prefix))
+        .map(|(_, words)| words)
+        .sum())
+}
+
+/// Sets (or clears, with `None`) the word-count goal of a category
+pub fn set_category_goal(project_path: &Path, category: &str, target: Option<usize>) -> Result<Project> {
+    let mut project = open_project(project_path)?;
+    let category = normalize_category(category);
+
+    match target {
+        Some(target) => project.settings.category_goals.insert(category, target),
+        None => project.settings.category_goals.remove(&category),
+    };
+
+    update_project(&project)?;
+    Ok(project)
+}
+
+/// Reports how far a category's prose is towards its word-count goal
+pub fn category_goal_progress(project_path: &Path, category: &str) -> Result<GoalProgress> {
+    let project = open_project(project_path)?;
+    let category = normalize_category(category);
+
+    let target = *project
+        .settings
+        .category_goals
+        .get(&category)
+        .ok_or_else(|| anyhow::anyhow!("No goal set for category: {}", category))?;
+
+    let current = category_word_count(project_path, &category)?;
+    let percentage = if target == 0 {
+        100.0
+    } else {
+        current as f64 / target as f64 * 100.0
+    };
+
+    Ok(GoalProgress {
+        target,
+        current,
+        percentage,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::project_service::create_project;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_category_goal_progress() {
+        let temp_dir = env::temp_dir().join("aycd_stats_test_goal");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let project = create_project("novel", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+
+        // 10 words in Drafts, plus words elsewhere that must not count
+        let drafts = project_path.join("NARRATIVE/Drafts");
+        fs::write(drafts.join("one.md"), "---\nid: a\n---\none two three four five six").unwrap();
+        fs::write(drafts.join("two.md"), "seven eight nine ten").unwrap();
+        fs::write(project_path.join("NARRATIVE/Final/done.md"), "not counted here").unwrap();
+        fs::create_dir_all(project_path.join("NARRATIVE/Drafts Old")).unwrap();
+        fs::write(project_path.join("NARRATIVE/Drafts Old/old.md"), "nor in a sibling folder").unwrap();
+
+        assert!(category_goal_progress(&project_path, "NARRATIVE/Drafts").is_err());
+
+        set_category_goal(&project_path, "NARRATIVE/Drafts", Some(40)).unwrap();
+        let progress = category_goal_progress(&project_path, "NARRATIVE/Drafts").unwrap();
+        assert_eq!(progress.target, 40);
+        assert_eq!(progress.current, 10);
+        assert_eq!(progress.percentage, 25.0);
+
+        // Counts come from the stats cache, which saves keep current
+        assert!(stats_cache_path(&project_path).exists());
+        fs::write(drafts.join("two.md"), "seven eight nine ten eleven twelve").unwrap();
+        record_document_words(&drafts.join("two.md"), None, Some(6));
+        assert_eq!(category_goal_progress(&project_path, "NARRATIVE/Drafts").unwrap().current, 12);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_cached_stats_match_recompute_after_edits() {
+        use crate::models::{DocumentType, ReplaceOptions};
+        use crate::services::document_service::{
+            create_document, delete_document, move_document, quick_save_with, replace_in_document, update_document,
+            MetricsThrottle,
+        };
+        use std::time::{Duration, Instant};
+
+        let temp_dir = env::temp_dir().join("aycd_stats_test_incremental");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let project = create_project("novel", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+        let one = create_document(&project_path, "One", DocumentType::Narrative, "Drafts", None).unwrap();
+        update_document(Path::new(&one.path), "---\ntitle: One\n---\n\nfirst draft words").unwrap();
+
+        // Builds the cache
+        let initial = project_stats(&project_path).unwrap();
+        assert_eq!(initial.document_count, 1);
+        assert_eq!(initial.narrative_words, 3);
+
+        let anna = create_document(&project_path, "Anna", DocumentType::World, "Cast", None).unwrap();
+        let two = create_document(&project_path, "Two", DocumentType::Narrative, "Drafts", None).unwrap();
+        update_document(Path::new(&anna.path), "Anna is tall and brave").unwrap();
+        // A local throttle keeps the shared one (and its flush thread) out of the test
+        let interval = Duration::from_millis(500);
+        let mut throttle = MetricsThrottle::new(interval);
+        let start = Instant::now();
+        quick_save_with(&mut throttle, Path::new(&two.path), "one two three four five six", start).unwrap();
+        let options = ReplaceOptions {
+            regex: false,
+            case_sensitive: true,
+            whole_word: true,
+        };
+        replace_in_document(Path::new(&one.path), "draft", "long draft of", &options, None).unwrap();
+        let moved = move_document(&project_path, Path::new(&two.path), "Final", None).unwrap();
+        update_document(Path::new(&moved.path), "six words in the final one").unwrap();
+        delete_document(Path::new(&one.path)).unwrap();
+
+        // Throttled quick saves reach the cache once their window closes
+        let three = create_document(&project_path, "Three", DocumentType::Narrative, "Drafts", None).unwrap();
+        let three_path = Path::new(&three.path);
+        quick_save_with(&mut throttle, three_path, "a b", start).unwrap();
+        quick_save_with(&mut throttle, three_path, "a b c d", start + Duration::from_millis(100)).unwrap();
+        quick_save_with(&mut throttle, three_path, "a b c d e", start + Duration::from_millis(200)).unwrap();
+        assert_eq!(project_stats(&project_path).unwrap().narrative_words, 8);
+        throttle.flush_due(start + interval);
+
+        let cached = project_stats(&project_path).unwrap();
+        let recomputed = recompute_stats(&project_path).unwrap();
+        assert_eq!(cached, recomputed);
+        assert_eq!(cached.document_count, 3);
+        assert_eq!(cached.world_words, 5);
+        assert_eq!(cached.narrative_words, 11);
+        assert_eq!(cached.total_words, 16);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+
+    #[test]
+    fn test_concurrent_saves_keep_cached_stats_exact() {
+        use crate::models::DocumentType;
+        use crate::services::document_service::{create_document, update_document};
+
+        let temp_dir = env::temp_dir().join("aycd_stats_test_concurrent");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let project = create_project("novel", Some(temp_dir.clone())).unwrap();
+        let project_path = PathBuf::from(&project.path);
+        let paths: Vec<PathBuf> = (0..8)
+            .map(|i| {
+                let doc = create_document(&project_path, &format!("Scene {}", i), DocumentType::Narrative, "Drafts", None)
+                    .unwrap();
+                PathBuf::from(doc.path)
+            })
+            .collect();
+        project_stats(&project_path).unwrap();
+
+        // Every thread's deltas must survive the others' load-modify-save cycles
+        std::thread::scope(|scope| {
+            for path in &paths {
+                scope.spawn(move || {
+                    for words in 1..=20 {
+                        update_document(path, &"word ".repeat(words)).unwrap();
+                    }
+                });
+            }
+        });
+
+        let cached = project_stats(&project_path).unwrap();
+        assert_eq!(cached, recompute_stats(&project_path).unwrap());
+        assert_eq!(cached.narrative_words, 8 * 20);
+
+        fs::remove_dir_all(&temp_dir).unwrap();
+    }
+}
@@ -15,6 +15,14 @@ export interface Document {
   createdAt: number;
   modifiedAt: number;
   metadata?: DocumentMetadata;
+  metricsStale?: boolean; // wordCount is from an earlier quick save and refreshes shortly
+}
+
+// Result of promote_document: non-drafts are left as-is with a message
+export interface PromoteOutcome {
+  document: Document;
+  promoted: boolean;
+  message?: string;
 }
 
 export interface DocumentMetadata {
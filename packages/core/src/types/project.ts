@@ -16,6 +16,8 @@ export interface ProjectSettings {
   defaultView?: 'editor' | 'canvas' | 'timeline';
   aiEnabled?: boolean;
   aiProvider?: string;
+  categoryGoals?: Record<string, number>; // e.g. { "NARRATIVE/Drafts": 50000 }
+  author?: string; // Overrides the app-wide default author
 }
 
 export interface CreateProjectInput {
@@ -23,3 +25,9 @@ export interface CreateProjectInput {
   path: string;
   template?: 'blank' | 'novel' | 'screenplay' | 'worldbuilding';
 }
+
+// Payload of the `project-renamed` event, emitted when a rename moves the folder
+export interface ProjectRenamed {
+  oldPath: string;
+  newPath: string;
+}